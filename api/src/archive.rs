@@ -0,0 +1,545 @@
+//! Offline access to Mender artifact (`.mender`) files.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+use chrono::{DateTime, FixedOffset, Utc};
+use flate2::read::GzDecoder;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use tar::Archive;
+use uuid::Uuid;
+
+pub use self::artifact_file::ArtifactFile;
+use self::hashing_reader::HashingReader;
+use self::header::{HeaderInfo, TypeInfo};
+pub use self::signature::{PublicKey, VerificationError};
+use crate::dto::{Artifact, Dependency, File, Info, TypeInfo as UpdateTypeInfo, Update};
+
+mod artifact_file;
+mod hashing_reader;
+mod header;
+mod signature;
+
+const VERSION: &str = "version";
+const MANIFEST: &str = "manifest";
+const MANIFEST_SIGNATURE: &str = "manifest.sig";
+const HEADER_PREFIX: &str = "header.tar";
+const HEADER_INFO: &str = "header-info";
+const TYPE_INFO: &str = "type-info";
+const META_DATA: &str = "meta-data";
+const DATA_PREFIX: &str = "data/";
+const ARTIFACT_NAME: &str = "artifact_name";
+const SUPPORTED_VERSION: u8 = 3;
+
+/// Parsed header of an artifact.
+#[derive(Debug)]
+struct Header {
+    info: HeaderInfo,
+    type_infos: BTreeMap<usize, TypeInfo>,
+    meta_data: BTreeMap<usize, Map<String, Value>>,
+}
+
+/// Open and parse the Mender artifact file at the given path.
+///
+/// # Errors
+///
+/// Returns an [`io::Error`] if the file cannot be read or is not a valid Mender artifact.
+pub fn open<P>(path: P) -> io::Result<ArtifactFile>
+where
+    P: AsRef<Path>,
+{
+    let file = fs::File::open(path)?;
+    let metadata = file.metadata()?;
+    let size = usize::try_from(metadata.len()).map_err(io::Error::other)?;
+    let modified = DateTime::<Utc>::from(metadata.modified()?).fixed_offset();
+    read(BufReader::new(file), size, modified)
+}
+
+/// Parse a Mender artifact of the given size and modification time from a reader.
+///
+/// The returned artifact has a nil ID, since it is not yet known to any server.
+///
+/// # Errors
+///
+/// Returns an [`io::Error`] if the artifact cannot be read or is not a valid Mender artifact.
+pub fn read<R>(reader: R, size: usize, modified: DateTime<FixedOffset>) -> io::Result<ArtifactFile>
+where
+    R: Read,
+{
+    let mut info: Option<Info> = None;
+    let mut manifest = None;
+    let mut manifest_signature = None;
+    let mut header = None;
+    let mut files = BTreeMap::new();
+    let mut checksums = BTreeMap::new();
+    let mut members = BTreeSet::new();
+
+    for entry in Archive::new(reader).entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+
+        // A later member of the same name would silently replace the one that was verified.
+        if !members.insert(path.clone()) {
+            return Err(invalid_data(format!("Duplicate member: {path}")));
+        }
+
+        match path.as_str() {
+            MANIFEST => {
+                let mut text = String::new();
+                entry.read_to_string(&mut text)?;
+                manifest.replace(text);
+            }
+            MANIFEST_SIGNATURE => {
+                let mut signature = Vec::new();
+                entry.read_to_end(&mut signature)?;
+                manifest_signature.replace(signature);
+            }
+            name => {
+                if let Some(name) = name.strip_prefix(DATA_PREFIX) {
+                    let index = index(name)?;
+
+                    if files
+                        .insert(index, read_payload(name, index, entry, &mut checksums)?)
+                        .is_some()
+                    {
+                        return Err(invalid_data(format!("Duplicate payload: {index}")));
+                    }
+                } else {
+                    let mut reader = HashingReader::new(entry);
+
+                    if name == VERSION {
+                        info.replace(serde_json::from_reader(&mut reader)?);
+                    } else if name.starts_with(HEADER_PREFIX) {
+                        header.replace(read_header(name, &mut reader)?);
+                    }
+
+                    checksums.insert(path, reader.finish()?);
+                }
+            }
+        }
+    }
+
+    let info = info.ok_or_else(|| invalid_data("Missing version"))?;
+
+    if info.format() != "mender" || info.version() != SUPPORTED_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("Unsupported artifact format: {info}"),
+        ));
+    }
+
+    let manifest = manifest.ok_or_else(|| invalid_data("Missing manifest"))?;
+    let mut header = header.ok_or_else(|| invalid_data("Missing header"))?;
+    let mut provides = header.info.artifact_provides;
+    let mut clears_provides = Vec::new();
+    let mut updates = Vec::with_capacity(header.info.payloads.len());
+    let mut meta_data = Vec::with_capacity(header.info.payloads.len());
+
+    for (index, payload) in header.info.payloads.into_iter().enumerate() {
+        let type_info = header.type_infos.remove(&index).unwrap_or_default();
+
+        for (key, value) in type_info.artifact_provides {
+            provides.insert(key, into_string(value));
+        }
+
+        clears_provides.extend(type_info.clears_artifact_provides);
+        updates.push(Update::new(
+            UpdateTypeInfo::new(payload.typ.or(type_info.typ).unwrap_or_default()),
+            files.remove(&index).unwrap_or_default(),
+        ));
+        meta_data.push(header.meta_data.remove(&index));
+    }
+
+    let name = provides
+        .get(ARTIFACT_NAME)
+        .cloned()
+        .ok_or_else(|| invalid_data("Missing artifact name"))?;
+    let signed = manifest_signature.is_some();
+    let artifact = Artifact::new(
+        Uuid::nil(),
+        None,
+        name,
+        header.info.artifact_depends.device_type.clone(),
+        info,
+        signed,
+        updates,
+        provides,
+        Dependency::new(header.info.artifact_depends.device_type),
+        clears_provides,
+        size,
+        modified,
+    );
+
+    Ok(ArtifactFile::new(
+        artifact,
+        manifest,
+        manifest_signature,
+        checksums,
+        meta_data,
+    ))
+}
+
+/// Read all payload files of a Mender artifact, computing their sizes and checksums.
+///
+/// # Errors
+///
+/// Returns an [`io::Error`] if the artifact cannot be read or uses an unsupported compression.
+pub fn payloads<R>(reader: R) -> io::Result<Vec<File>>
+where
+    R: Read,
+{
+    let mut payloads = Vec::new();
+
+    for entry in Archive::new(reader).entries()? {
+        let entry = entry?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+
+        if let Some(name) = path.strip_prefix(DATA_PREFIX) {
+            payloads.extend(read_files(name, entry)?);
+        }
+    }
+
+    Ok(payloads)
+}
+
+/// Read the files of a payload archive, recording their checksums under their manifest paths.
+///
+/// The manifest lists the files inside of each payload archive,
+/// along with the checksums of their uncompressed contents.
+fn read_payload<R>(
+    name: &str,
+    index: usize,
+    reader: R,
+    checksums: &mut BTreeMap<String, String>,
+) -> io::Result<Vec<File>>
+where
+    R: Read,
+{
+    let files = read_files(name, reader)?;
+
+    for file in &files {
+        let name = format!("{DATA_PREFIX}{index:04}/{}", file.name());
+
+        if checksums
+            .insert(name.clone(), file.checksum().to_string())
+            .is_some()
+        {
+            return Err(invalid_data(format!("Duplicate payload file: {name}")));
+        }
+    }
+
+    Ok(files)
+}
+
+/// Read the (possibly compressed) header archive.
+fn read_header<R>(name: &str, reader: R) -> io::Result<Header>
+where
+    R: Read,
+{
+    let mut info = None;
+    let mut type_infos = BTreeMap::new();
+    let mut meta_data = BTreeMap::new();
+
+    for entry in Archive::new(decompress(name, reader)?).entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+
+        if path == HEADER_INFO {
+            info.replace(serde_json::from_reader(&mut entry)?);
+        } else if let Some((index, file)) = path
+            .strip_prefix("headers/")
+            .and_then(|path| path.split_once('/'))
+        {
+            let index = self::index(index)?;
+
+            match file {
+                TYPE_INFO => {
+                    type_infos.insert(index, serde_json::from_reader(&mut entry)?);
+                }
+                META_DATA => {
+                    let mut text = String::new();
+                    entry.read_to_string(&mut text)?;
+
+                    if !text.trim().is_empty() {
+                        meta_data.insert(index, serde_json::from_str(&text)?);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    Ok(Header {
+        info: info.ok_or_else(|| invalid_data("Missing header info"))?,
+        type_infos,
+        meta_data,
+    })
+}
+
+/// Read the files of a (possibly compressed) payload archive, computing their sizes and checksums.
+fn read_files<R>(name: &str, reader: R) -> io::Result<Vec<File>>
+where
+    R: Read,
+{
+    let mut files = Vec::new();
+
+    for entry in Archive::new(decompress(name, reader)?).entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let date = i64::try_from(entry.header().mtime()?)
+            .ok()
+            .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+            .unwrap_or_default()
+            .fixed_offset();
+        let mut hasher = Sha256::new();
+        let size = io::copy(&mut entry, &mut hasher)?;
+        files.push(File::new(
+            name,
+            format!("{:x}", hasher.finalize()),
+            usize::try_from(size).map_err(io::Error::other)?,
+            date,
+        ));
+    }
+
+    Ok(files)
+}
+
+/// Wrap the reader of a possibly compressed archive member into a decompressing reader.
+fn decompress<'reader, R>(name: &str, reader: R) -> io::Result<Box<dyn Read + 'reader>>
+where
+    R: Read + 'reader,
+{
+    match Path::new(name).extension().and_then(|ext| ext.to_str()) {
+        Some("tar") => Ok(Box::new(reader)),
+        Some("gz") => Ok(Box::new(GzDecoder::new(reader))),
+        other => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("Unsupported compression of {name}: {other:?}"),
+        )),
+    }
+}
+
+/// Parse the payload index from a name such as `0000.tar.gz` or `0000`.
+fn index(name: &str) -> io::Result<usize> {
+    name.split('.')
+        .next()
+        .unwrap_or(name)
+        .parse()
+        .map_err(|_| invalid_data(format!("Invalid payload index: {name}")))
+}
+
+/// Convert a JSON value into a string, without quoting strings.
+fn into_string(value: Value) -> String {
+    match value {
+        Value::String(string) => string,
+        other => other.to_string(),
+    }
+}
+
+/// Create an [`io::Error`] signalling invalid data.
+fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read, Write};
+
+    use chrono::DateTime;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use tar::{Archive, Builder, Header};
+
+    use super::{ArtifactFile, PublicKey, VerificationError, read};
+
+    const ARTIFACT: &[u8] = include_bytes!("../tests/fixtures/signed.mender");
+    const PUBLIC_KEY: &str = include_str!("../tests/fixtures/signed.pub.pem");
+    const OTHER_PUBLIC_KEY: &str = include_str!("../tests/fixtures/other.pub.pem");
+    const PAYLOAD: &str = "data/0000.tar.gz";
+
+    fn parse(artifact: &[u8]) -> io::Result<ArtifactFile> {
+        read(
+            artifact,
+            artifact.len(),
+            DateTime::UNIX_EPOCH.fixed_offset(),
+        )
+    }
+
+    fn key(pem: &str) -> PublicKey {
+        PublicKey::from_pem(pem).expect("fixture key should parse")
+    }
+
+    /// Build a tar archive of the given members.
+    fn archive<'member, I>(members: I) -> Vec<u8>
+    where
+        I: IntoIterator<Item = (&'member str, &'member [u8])>,
+    {
+        let mut builder = Builder::new(Vec::new());
+
+        for (name, data) in members {
+            let mut header = Header::new_ustar();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, data)
+                .expect("member should be appended");
+        }
+
+        builder.into_inner().expect("archive should be finished")
+    }
+
+    /// Build a compressed payload archive of the given files.
+    fn payload(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&archive(files.iter().copied()))
+            .expect("payload should be compressed");
+        encoder.finish().expect("payload should be finished")
+    }
+
+    /// Rebuild the fixture, replacing or dropping members and appending others.
+    fn rebuild<F>(mut replace: F, append: &[(&str, &[u8])]) -> Vec<u8>
+    where
+        F: FnMut(&str, Vec<u8>) -> Option<Vec<u8>>,
+    {
+        let mut members = Vec::new();
+
+        for entry in Archive::new(ARTIFACT).entries().expect("fixture is a tar") {
+            let mut entry = entry.expect("fixture member should be readable");
+            let name = entry
+                .path()
+                .expect("fixture member has a path")
+                .to_string_lossy()
+                .into_owned();
+            let mut data = Vec::new();
+            entry
+                .read_to_end(&mut data)
+                .expect("fixture member should be readable");
+
+            if let Some(data) = replace(&name, data) {
+                members.push((name, data));
+            }
+        }
+
+        members.extend(
+            append
+                .iter()
+                .map(|(name, data)| ((*name).to_owned(), data.to_vec())),
+        );
+        archive(
+            members
+                .iter()
+                .map(|(name, data)| (name.as_str(), data.as_slice())),
+        )
+    }
+
+    #[test]
+    fn verifies_signed_artifact() {
+        let artifact_file = parse(ARTIFACT).expect("fixture should parse");
+
+        assert_eq!(artifact_file.artifact().name(), "fixture-1.0");
+        assert!(
+            artifact_file
+                .checksums()
+                .contains_key("data/0000/rootfs.ext4")
+        );
+        assert_eq!(artifact_file.verify(&key(PUBLIC_KEY)), Ok(()));
+    }
+
+    #[test]
+    fn rejects_tampered_payload() {
+        let tampered = payload(&[("rootfs.ext4", b"Hello, attacker!\n")]);
+        let artifact = rebuild(
+            |name, data| {
+                Some(if name == PAYLOAD {
+                    tampered.clone()
+                } else {
+                    data
+                })
+            },
+            &[],
+        );
+        let artifact_file = parse(&artifact).expect("tampered artifact should parse");
+
+        assert!(matches!(
+            artifact_file.verify(&key(PUBLIC_KEY)),
+            Err(VerificationError::ChecksumMismatch { name, .. }) if name == "data/0000/rootfs.ext4"
+        ));
+    }
+
+    #[test]
+    fn rejects_wrong_key() {
+        let artifact_file = parse(ARTIFACT).expect("fixture should parse");
+
+        assert!(matches!(
+            artifact_file.verify(&key(OTHER_PUBLIC_KEY)),
+            Err(VerificationError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_signature() {
+        let artifact = rebuild(|name, data| (name != "manifest.sig").then_some(data), &[]);
+        let artifact_file = parse(&artifact).expect("unsigned artifact should parse");
+
+        assert!(!artifact_file.artifact().signed());
+        assert_eq!(
+            artifact_file.verify(&key(PUBLIC_KEY)),
+            Err(VerificationError::Unsigned)
+        );
+    }
+
+    #[test]
+    fn rejects_unlisted_file() {
+        for extra in ["extra.bin", "evil-augment.bin"] {
+            let extended = payload(&[("rootfs.ext4", b"Hello, Mender!\n"), (extra, b"unsigned")]);
+            let artifact = rebuild(
+                |name, data| {
+                    Some(if name == PAYLOAD {
+                        extended.clone()
+                    } else {
+                        data
+                    })
+                },
+                &[],
+            );
+            let artifact_file = parse(&artifact).expect("extended artifact should parse");
+
+            assert_eq!(
+                artifact_file.verify(&key(PUBLIC_KEY)),
+                Err(VerificationError::UnlistedFile(format!(
+                    "data/0000/{extra}"
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn ignores_augmented_header() {
+        let artifact = rebuild(
+            |_, data| Some(data),
+            &[("header-augment.tar.gz", b"augmented")],
+        );
+        let artifact_file = parse(&artifact).expect("augmented artifact should parse");
+
+        assert_eq!(artifact_file.verify(&key(PUBLIC_KEY)), Ok(()));
+    }
+
+    #[test]
+    fn rejects_duplicate_member() {
+        let duplicate = payload(&[("rootfs.ext4", b"Hello, attacker!\n")]);
+        let artifact = rebuild(|_, data| Some(data), &[(PAYLOAD, &duplicate)]);
+
+        assert_eq!(
+            parse(&artifact).map_err(|error| error.kind()).err(),
+            Some(io::ErrorKind::InvalidData)
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+
+use super::{PublicKey, VerificationError};
+use crate::dto::Artifact;
+
+/// Members of augmented artifacts, which are not covered by the signed manifest.
+const AUGMENT_MEMBERS: [&str; 5] = [
+    "manifest-augment",
+    "header-augment.tar",
+    "header-augment.tar.gz",
+    "header-augment.tar.xz",
+    "header-augment.tar.zst",
+];
+
+/// A Mender artifact file read from disk.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArtifactFile {
+    artifact: Artifact,
+    manifest: String,
+    manifest_signature: Option<Vec<u8>>,
+    checksums: BTreeMap<String, String>,
+    meta_data: Vec<Option<Map<String, Value>>>,
+}
+
+impl ArtifactFile {
+    /// Creates a new `ArtifactFile` instance.
+    #[must_use]
+    pub const fn new(
+        artifact: Artifact,
+        manifest: String,
+        manifest_signature: Option<Vec<u8>>,
+        checksums: BTreeMap<String, String>,
+        meta_data: Vec<Option<Map<String, Value>>>,
+    ) -> Self {
+        Self {
+            artifact,
+            manifest,
+            manifest_signature,
+            checksums,
+            meta_data,
+        }
+    }
+
+    /// Returns the artifact as it would be described by the Mender server.
+    #[must_use]
+    pub const fn artifact(&self) -> &Artifact {
+        &self.artifact
+    }
+
+    /// Returns the manifest, listing the checksums of the artifact's members.
+    #[must_use]
+    pub fn manifest(&self) -> &str {
+        &self.manifest
+    }
+
+    /// Returns the signature of the manifest, if the artifact is signed.
+    #[must_use]
+    pub fn manifest_signature(&self) -> Option<&[u8]> {
+        self.manifest_signature.as_deref()
+    }
+
+    /// Returns the checksums of the artifact's members, as computed while reading it.
+    ///
+    /// Payload files are keyed by their path in the manifest, such as `data/0000/rootfs.ext4`,
+    /// and checksummed over their uncompressed contents.
+    #[must_use]
+    pub const fn checksums(&self) -> &BTreeMap<String, String> {
+        &self.checksums
+    }
+
+    /// Returns the meta data of each payload, if any.
+    #[must_use]
+    pub fn meta_data(&self) -> &[Option<Map<String, Value>>] {
+        &self.meta_data
+    }
+}
+
+/// Verification.
+impl ArtifactFile {
+    /// Verify that the artifact's members match the checksums listed in its manifest.
+    ///
+    /// Augmented members are not covered by the manifest and thus are not checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`VerificationError`] if the manifest is malformed or does not match.
+    pub fn verify_manifest(&self) -> Result<(), VerificationError> {
+        let mut listed = BTreeMap::new();
+
+        for line in self.manifest.lines().filter(|line| !line.trim().is_empty()) {
+            let mut fields = line.split_whitespace();
+            let (Some(checksum), Some(name), None) = (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(VerificationError::MalformedManifest(line.to_string()));
+            };
+
+            listed.insert(name, checksum);
+        }
+
+        for (name, expected) in &listed {
+            let Some(actual) = self.checksums.get(*name) else {
+                return Err(VerificationError::MissingFile((*name).to_string()));
+            };
+
+            if !actual.eq_ignore_ascii_case(expected) {
+                return Err(VerificationError::ChecksumMismatch {
+                    name: (*name).to_string(),
+                    expected: (*expected).to_string(),
+                    actual: actual.clone(),
+                });
+            }
+        }
+
+        if let Some(name) = self.checksums.keys().find(|name| {
+            !listed.contains_key(name.as_str()) && !AUGMENT_MEMBERS.contains(&name.as_str())
+        }) {
+            return Err(VerificationError::UnlistedFile(name.clone()));
+        }
+
+        Ok(())
+    }
+
+    /// Verify the artifact's manifest signature against the given public key
+    /// and its members against the manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`VerificationError`] describing the first check that failed.
+    pub fn verify(&self, key: &PublicKey) -> Result<(), VerificationError> {
+        let signature = self
+            .manifest_signature
+            .as_deref()
+            .ok_or(VerificationError::Unsigned)?;
+        key.verify(self.manifest.as_bytes(), signature)?;
+        self.verify_manifest()
+    }
+}
+
+impl From<ArtifactFile> for Artifact {
+    fn from(artifact_file: ArtifactFile) -> Self {
+        artifact_file.artifact
+    }
+}
@@ -0,0 +1,40 @@
+use std::io::{self, Read};
+
+use sha2::{Digest, Sha256};
+
+/// A reader computing the SHA-256 checksum of all data read through it.
+#[derive(Debug)]
+pub struct HashingReader<R> {
+    reader: R,
+    hasher: Sha256,
+}
+
+impl<R> HashingReader<R>
+where
+    R: Read,
+{
+    /// Creates a new `HashingReader` wrapping the given reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Consume the remaining data and return the hex-encoded checksum of everything read.
+    pub fn finish(mut self) -> io::Result<String> {
+        io::copy(&mut self, &mut io::sink())?;
+        Ok(format!("{:x}", self.hasher.finalize()))
+    }
+}
+
+impl<R> Read for HashingReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.reader.read(buf)?;
+        self.hasher.update(&buf[..len]);
+        Ok(len)
+    }
+}
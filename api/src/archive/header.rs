@@ -0,0 +1,41 @@
+//! Headers of Mender artifact files.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::dto::DeviceType;
+
+/// The `header-info` file of an artifact's header.
+#[derive(Clone, Debug, Deserialize)]
+pub struct HeaderInfo {
+    pub payloads: Vec<PayloadInfo>,
+    pub artifact_provides: BTreeMap<String, String>,
+    pub artifact_depends: Depends,
+}
+
+/// A payload entry of the `header-info` file.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PayloadInfo {
+    #[serde(rename = "type")]
+    pub typ: Option<String>,
+}
+
+/// Dependencies of an artifact.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Depends {
+    #[serde(default)]
+    pub device_type: Vec<DeviceType>,
+}
+
+/// The `type-info` file of a payload's header.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct TypeInfo {
+    #[serde(rename = "type")]
+    pub typ: Option<String>,
+    #[serde(default)]
+    pub artifact_provides: BTreeMap<String, Value>,
+    #[serde(default)]
+    pub clears_artifact_provides: Vec<String>,
+}
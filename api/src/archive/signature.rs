@@ -0,0 +1,136 @@
+//! Verification of artifact signatures.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+use p256::pkcs8::DecodePublicKey;
+use rsa::RsaPublicKey;
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs1v15::Pkcs1v15Sign;
+use sha2::{Digest, Sha256};
+
+/// A public key to verify artifact signatures with.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PublicKey {
+    /// An RSA public key.
+    Rsa(RsaPublicKey),
+    /// An ECDSA public key on the NIST P-256 curve.
+    Ecdsa(VerifyingKey),
+}
+
+impl PublicKey {
+    /// Parse a public key in PEM format.
+    ///
+    /// Supports RSA keys in PKCS#1 or SPKI format, and ECDSA P-256 keys in SPKI format.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`VerificationError::InvalidKey`] if the key cannot be parsed.
+    pub fn from_pem(pem: &str) -> Result<Self, VerificationError> {
+        if let Ok(key) = RsaPublicKey::from_public_key_pem(pem) {
+            return Ok(Self::Rsa(key));
+        }
+
+        if let Ok(key) = RsaPublicKey::from_pkcs1_pem(pem) {
+            return Ok(Self::Rsa(key));
+        }
+
+        VerifyingKey::from_public_key_pem(pem)
+            .map(Self::Ecdsa)
+            .map_err(|error| VerificationError::InvalidKey(error.to_string()))
+    }
+
+    /// Verify the base64-encoded `signature` of `message`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`VerificationError`] if the signature is malformed or invalid.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), VerificationError> {
+        let signature = BASE64_STANDARD
+            .decode(signature.trim_ascii())
+            .map_err(|error| VerificationError::MalformedSignature(error.to_string()))?;
+
+        match self {
+            Self::Rsa(key) => key
+                .verify(
+                    Pkcs1v15Sign::new::<Sha256>(),
+                    &Sha256::digest(message),
+                    &signature,
+                )
+                .map_err(|error| VerificationError::InvalidSignature(error.to_string())),
+            Self::Ecdsa(key) => {
+                // Mender encodes ECDSA signatures as the concatenation of r and s,
+                // but DER-encoded signatures are accepted as well.
+                let signature = Signature::from_slice(&signature)
+                    .or_else(|_| Signature::from_der(&signature))
+                    .map_err(|error| VerificationError::MalformedSignature(error.to_string()))?;
+                key.verify(message, &signature)
+                    .map_err(|error| VerificationError::InvalidSignature(error.to_string()))
+            }
+        }
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = VerificationError;
+
+    fn from_str(pem: &str) -> Result<Self, Self::Err> {
+        Self::from_pem(pem)
+    }
+}
+
+/// Errors that can occur when verifying an artifact.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VerificationError {
+    /// The public key could not be parsed.
+    InvalidKey(String),
+    /// The artifact is not signed.
+    Unsigned,
+    /// The signature could not be decoded.
+    MalformedSignature(String),
+    /// The signature does not match the manifest and key.
+    InvalidSignature(String),
+    /// A line of the manifest could not be parsed.
+    MalformedManifest(String),
+    /// A file listed in the manifest is missing from the artifact.
+    MissingFile(String),
+    /// A file of the artifact is not listed in the manifest.
+    UnlistedFile(String),
+    /// A file's checksum does not match the checksum listed in the manifest.
+    ChecksumMismatch {
+        /// The name of the file.
+        name: String,
+        /// The checksum listed in the manifest.
+        expected: String,
+        /// The actual checksum of the file.
+        actual: String,
+    },
+}
+
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidKey(error) => write!(f, "Invalid public key: {error}"),
+            Self::Unsigned => write!(f, "Artifact is not signed"),
+            Self::MalformedSignature(error) => write!(f, "Malformed signature: {error}"),
+            Self::InvalidSignature(error) => write!(f, "Invalid signature: {error}"),
+            Self::MalformedManifest(line) => write!(f, "Malformed manifest line: {line}"),
+            Self::MissingFile(name) => write!(f, "File listed in manifest is missing: {name}"),
+            Self::UnlistedFile(name) => write!(f, "File is not listed in manifest: {name}"),
+            Self::ChecksumMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Checksum mismatch of {name}: expected {expected}, got {actual}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
@@ -1,14 +1,28 @@
 //! Manage artifacts.
 
+use std::fs::File;
+use std::io::{self, BufReader};
 use std::num::NonZero;
+use std::path::Path;
 
+use log::warn;
+use tokio::io::AsyncRead;
+use tokio::task::spawn_blocking;
 use uuid::Uuid;
 
-use crate::dto::Artifact;
+pub use self::download::DownloadError;
+pub use self::generation::ArtifactGeneration;
+pub use self::upload::ArtifactUpload;
+use crate::archive::payloads;
+use crate::dto::{Artifact, DownloadLink};
 use crate::pagination::DEFAULT_PAGE_SIZE;
-use crate::utils::ResponseExt;
+use crate::utils::{RequestBuilderExt, ResponseExt};
 use crate::{Pager, PaginatedIterator, Session};
 
+mod download;
+mod generation;
+mod upload;
+
 const PATH: &str = "api/management/v1/deployments/artifacts";
 
 /// Artifacts management API.
@@ -16,8 +30,63 @@ pub trait Artifacts {
     /// List all artifacts.
     fn list(&self, page_size: Option<NonZero<usize>>) -> PaginatedIterator<'_, '_, Artifact>;
 
+    /// Get an artifact by its ID.
+    fn get(&self, id: Uuid) -> impl Future<Output = crate::Result<Artifact>> + Send;
+
     /// Delete an artifact by its ID.
-    fn delete(&self, id: Uuid) -> impl Future<Output = reqwest::Result<()>>;
+    fn delete(&self, id: Uuid) -> impl Future<Output = crate::Result<()>>;
+
+    /// Upload an artifact.
+    ///
+    /// The `progress` callback is invoked with the total amount of bytes sent so far.
+    ///
+    /// # Returns
+    ///
+    /// Returns the ID of the uploaded artifact, if the server reported it.
+    fn upload<R, F>(
+        &self,
+        upload: ArtifactUpload<R>,
+        progress: F,
+    ) -> impl Future<Output = crate::Result<Option<Uuid>>> + Send
+    where
+        R: AsyncRead + Send + 'static,
+        F: FnMut(u64) + Send + 'static;
+
+    /// Let the server generate an artifact from a raw payload file.
+    ///
+    /// The `progress` callback is invoked with the total amount of bytes sent so far.
+    ///
+    /// # Returns
+    ///
+    /// Returns the ID of the generated artifact, if the server reported it.
+    fn generate<R, F>(
+        &self,
+        generation: ArtifactGeneration<R>,
+        progress: F,
+    ) -> impl Future<Output = crate::Result<Option<Uuid>>> + Send
+    where
+        R: AsyncRead + Send + 'static,
+        F: FnMut(u64) + Send + 'static;
+
+    /// Get a time-limited download link for an artifact.
+    fn download_link(&self, id: Uuid) -> impl Future<Output = crate::Result<DownloadLink>> + Send;
+
+    /// Download an artifact to the given path and verify its payloads against the checksums
+    /// reported by the server.
+    ///
+    /// The artifact is downloaded to a temporary sibling of `path` and only moved to `path` once
+    /// its payloads have been verified.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DownloadError`] if the download fails or the payloads do not match.
+    fn download<P>(
+        &self,
+        artifact: &Artifact,
+        path: P,
+    ) -> impl Future<Output = Result<(), DownloadError>> + Send
+    where
+        P: AsRef<Path> + Send + Sync;
 }
 
 impl Artifacts for Session {
@@ -30,12 +99,105 @@ impl Artifacts for Session {
         .into()
     }
 
-    async fn delete(&self, id: Uuid) -> reqwest::Result<()> {
+    async fn get(&self, id: Uuid) -> crate::Result<Artifact> {
+        self.get(format!("{PATH}/{id}"), None)
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .decode()
+            .await
+    }
+
+    async fn delete(&self, id: Uuid) -> crate::Result<()> {
         self.delete(format!("{PATH}/{id}"), None)
-            .send()
+            .send_with(self)
+            .await?
+            .check_status()
             .await?
-            .error_for_status()?
             .ensure_empty()
             .await
     }
+
+    async fn upload<R, F>(
+        &self,
+        upload: ArtifactUpload<R>,
+        progress: F,
+    ) -> crate::Result<Option<Uuid>>
+    where
+        R: AsyncRead + Send + 'static,
+        F: FnMut(u64) + Send + 'static,
+    {
+        self.post(PATH, None)
+            .multipart(upload.into_form(progress))
+            .send_with(self)
+            .await?
+            .check_status()
+            .await
+            .map(|response| response.created_id())
+    }
+
+    async fn generate<R, F>(
+        &self,
+        generation: ArtifactGeneration<R>,
+        progress: F,
+    ) -> crate::Result<Option<Uuid>>
+    where
+        R: AsyncRead + Send + 'static,
+        F: FnMut(u64) + Send + 'static,
+    {
+        self.post(format!("{PATH}/generate"), None)
+            .multipart(generation.into_form(progress))
+            .send_with(self)
+            .await?
+            .check_status()
+            .await
+            .map(|response| response.created_id())
+    }
+
+    async fn download_link(&self, id: Uuid) -> crate::Result<DownloadLink> {
+        self.get(format!("{PATH}/{id}/download"), None)
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .decode()
+            .await
+    }
+
+    async fn download<P>(&self, artifact: &Artifact, path: P) -> Result<(), DownloadError>
+    where
+        P: AsRef<Path> + Send + Sync,
+    {
+        let link = self.download_link(artifact.id()).await?;
+        // The link is pre-signed, so it must not be sent with our bearer token.
+        let response = self
+            .retry_policy()
+            .send(self.client().get(link.uri()), false)
+            .await?
+            .check_status()
+            .await?;
+        // Download to a sibling file first, so that a corrupt download never ends up at `path`.
+        let partial = download::partial_path(path.as_ref());
+        let result = async {
+            download::fetch(response, &partial).await?;
+            let file = partial.clone();
+            let payloads = spawn_blocking(move || payloads(BufReader::new(File::open(file)?)))
+                .await
+                .map_err(io::Error::other)??;
+            download::verify(artifact, &payloads)
+        }
+        .await;
+
+        if let Err(error) = result {
+            if let Err(remove_error) = tokio::fs::remove_file(&partial).await {
+                warn!("Failed to remove {}: {remove_error}", partial.display());
+            }
+
+            return Err(error);
+        }
+
+        tokio::fs::rename(&partial, path.as_ref()).await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,150 @@
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use reqwest::Response;
+use tokio::io::AsyncWriteExt;
+
+use crate::dto::{Artifact, File, Update};
+
+/// Errors that can occur when downloading an artifact.
+#[derive(Debug)]
+pub enum DownloadError {
+    /// The HTTP request failed.
+    Request(crate::Error),
+    /// Writing or reading the local file failed.
+    Io(io::Error),
+    /// A payload file listed by the server is missing from the downloaded artifact.
+    MissingPayload {
+        /// The name of the missing payload file.
+        name: String,
+    },
+    /// A payload file's size does not match the size reported by the server.
+    SizeMismatch {
+        /// The name of the payload file.
+        name: String,
+        /// The size reported by the server.
+        expected: usize,
+        /// The size of the downloaded payload file.
+        actual: usize,
+    },
+    /// A payload file's checksum does not match the checksum reported by the server.
+    ChecksumMismatch {
+        /// The name of the payload file.
+        name: String,
+        /// The checksum reported by the server.
+        expected: String,
+        /// The checksum of the downloaded payload file.
+        actual: String,
+    },
+}
+
+impl Display for DownloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Request(error) => write!(f, "Request failed: {error}"),
+            Self::Io(error) => write!(f, "I/O error: {error}"),
+            Self::MissingPayload { name } => write!(f, "Missing payload file: {name}"),
+            Self::SizeMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Size mismatch of {name}: expected {expected} bytes, got {actual} bytes"
+            ),
+            Self::ChecksumMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Checksum mismatch of {name}: expected {expected}, got {actual}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DownloadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Request(error) => Some(error),
+            Self::Io(error) => Some(error),
+            Self::MissingPayload { .. }
+            | Self::SizeMismatch { .. }
+            | Self::ChecksumMismatch { .. } => None,
+        }
+    }
+}
+
+impl From<crate::Error> for DownloadError {
+    fn from(error: crate::Error) -> Self {
+        Self::Request(error)
+    }
+}
+
+impl From<reqwest::Error> for DownloadError {
+    fn from(error: reqwest::Error) -> Self {
+        Self::Request(error.into())
+    }
+}
+
+impl From<io::Error> for DownloadError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Verify the payloads of a downloaded artifact against the files listed by the server.
+pub fn verify(artifact: &Artifact, payloads: &[File]) -> Result<(), DownloadError> {
+    for file in artifact.updates().iter().flat_map(Update::files) {
+        let Some(payload) = payloads
+            .iter()
+            .find(|payload| payload.name() == file.name())
+        else {
+            return Err(DownloadError::MissingPayload {
+                name: file.name().to_string(),
+            });
+        };
+
+        if payload.size() != file.size() {
+            return Err(DownloadError::SizeMismatch {
+                name: file.name().to_string(),
+                expected: file.size(),
+                actual: payload.size(),
+            });
+        }
+
+        if !payload.checksum().eq_ignore_ascii_case(file.checksum()) {
+            return Err(DownloadError::ChecksumMismatch {
+                name: file.name().to_string(),
+                expected: file.checksum().to_string(),
+                actual: payload.checksum().to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Return the path of the temporary file that an artifact is downloaded to before it is verified.
+pub fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path
+        .file_name()
+        .map_or_else(|| OsString::from("artifact"), ToOwned::to_owned);
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+/// Write the body of the response to the given path.
+pub async fn fetch(mut response: Response, path: &Path) -> Result<(), DownloadError> {
+    let mut file = tokio::fs::File::create(path).await?;
+
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await?;
+    }
+
+    file.flush().await?;
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use std::io;
+use std::path::Path;
+
+use reqwest::multipart::Form;
+use tokio::fs::File;
+use tokio::io::AsyncRead;
+
+use super::upload::{file_name, stream_part};
+use crate::dto::DeviceType;
+
+const DEFAULT_FILE_NAME: &str = "payload";
+
+/// A request to let the Mender server generate an artifact from a raw payload file.
+#[derive(Debug)]
+pub struct ArtifactGeneration<R> {
+    name: String,
+    update_type: String,
+    reader: R,
+    file_name: String,
+    size: Option<u64>,
+    description: Option<String>,
+    compatible_device_types: Vec<DeviceType>,
+    args: Option<String>,
+}
+
+impl<R> ArtifactGeneration<R> {
+    /// Creates a new `ArtifactGeneration` reading the payload from the given reader.
+    #[must_use]
+    pub const fn new(name: String, update_type: String, reader: R, file_name: String) -> Self {
+        Self {
+            name,
+            update_type,
+            reader,
+            file_name,
+            size: None,
+            description: None,
+            compatible_device_types: Vec::new(),
+            args: None,
+        }
+    }
+
+    /// Set the size of the payload in bytes.
+    #[must_use]
+    pub const fn with_size(mut self, size: u64) -> Self {
+        self.size.replace(size);
+        self
+    }
+
+    /// Set the description of the artifact.
+    #[must_use]
+    pub fn with_description(mut self, description: String) -> Self {
+        self.description.replace(description);
+        self
+    }
+
+    /// Set the device types the artifact is compatible with.
+    #[must_use]
+    pub fn with_compatible_device_types(mut self, device_types: Vec<DeviceType>) -> Self {
+        self.compatible_device_types = device_types;
+        self
+    }
+
+    /// Set the arguments for the update module as a JSON string.
+    #[must_use]
+    pub fn with_args(mut self, args: String) -> Self {
+        self.args.replace(args);
+        self
+    }
+
+    /// Returns the size of the payload in bytes, if known.
+    #[must_use]
+    pub const fn size(&self) -> Option<u64> {
+        self.size
+    }
+}
+
+impl ArtifactGeneration<File> {
+    /// Open the payload file at the given path for generating an artifact.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file cannot be opened or its metadata cannot be read.
+    pub async fn open<P>(name: String, update_type: String, path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = File::open(path).await?;
+        let size = file.metadata().await?.len();
+        Ok(Self::new(name, update_type, file, file_name(path, DEFAULT_FILE_NAME)).with_size(size))
+    }
+}
+
+impl<R> ArtifactGeneration<R>
+where
+    R: AsyncRead + Send + 'static,
+{
+    /// Convert the generation request into a multipart form.
+    ///
+    /// The `progress` callback is invoked with the total amount of bytes read so far.
+    pub(crate) fn into_form<F>(self, progress: F) -> Form
+    where
+        F: FnMut(u64) + Send + 'static,
+    {
+        let part = stream_part(self.reader, self.file_name, self.size, progress);
+        let mut form = Form::new()
+            .text("name", self.name)
+            .text("type", self.update_type);
+
+        if let Some(description) = self.description {
+            form = form.text("description", description);
+        }
+
+        for device_type in self.compatible_device_types {
+            form = form.text("device_types_compatible", device_type.to_string());
+        }
+
+        if let Some(args) = self.args {
+            form = form.text("args", args);
+        }
+
+        if let Some(size) = self.size {
+            form = form.text("size", size.to_string());
+        }
+
+        // The payload must be the last part of the form.
+        form.part("file", part)
+    }
+}
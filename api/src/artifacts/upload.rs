@@ -0,0 +1,126 @@
+use std::io;
+use std::path::Path;
+
+use futures_util::TryStreamExt;
+use reqwest::Body;
+use reqwest::multipart::{Form, Part};
+use tokio::fs::File;
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
+
+const DEFAULT_FILE_NAME: &str = "artifact.mender";
+
+/// An artifact to be uploaded to the Mender server.
+#[derive(Debug)]
+pub struct ArtifactUpload<R> {
+    reader: R,
+    file_name: String,
+    size: Option<u64>,
+    description: Option<String>,
+}
+
+impl<R> ArtifactUpload<R> {
+    /// Creates a new `ArtifactUpload` reading the artifact from the given reader.
+    #[must_use]
+    pub const fn new(reader: R, file_name: String) -> Self {
+        Self {
+            reader,
+            file_name,
+            size: None,
+            description: None,
+        }
+    }
+
+    /// Set the size of the artifact in bytes.
+    #[must_use]
+    pub const fn with_size(mut self, size: u64) -> Self {
+        self.size.replace(size);
+        self
+    }
+
+    /// Set the description of the artifact.
+    #[must_use]
+    pub fn with_description(mut self, description: String) -> Self {
+        self.description.replace(description);
+        self
+    }
+
+    /// Returns the size of the artifact in bytes, if known.
+    #[must_use]
+    pub const fn size(&self) -> Option<u64> {
+        self.size
+    }
+}
+
+impl ArtifactUpload<File> {
+    /// Open the artifact file at the given path for uploading.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file cannot be opened or its metadata cannot be read.
+    pub async fn open<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = File::open(path).await?;
+        let size = file.metadata().await?.len();
+        Ok(Self::new(file, file_name(path, DEFAULT_FILE_NAME)).with_size(size))
+    }
+}
+
+impl<R> ArtifactUpload<R>
+where
+    R: AsyncRead + Send + 'static,
+{
+    /// Convert the upload into a multipart form.
+    ///
+    /// The `progress` callback is invoked with the total amount of bytes read so far.
+    pub(crate) fn into_form<F>(self, progress: F) -> Form
+    where
+        F: FnMut(u64) + Send + 'static,
+    {
+        let part = stream_part(self.reader, self.file_name, self.size, progress);
+        let mut form = Form::new();
+
+        if let Some(description) = self.description {
+            form = form.text("description", description);
+        }
+
+        if let Some(size) = self.size {
+            form = form.text("size", size.to_string());
+        }
+
+        // The artifact must be the last part of the form.
+        form.part("artifact", part)
+    }
+}
+
+/// Create a multipart part streaming a file from the given reader.
+///
+/// The `progress` callback is invoked with the total amount of bytes read so far.
+pub fn stream_part<R, F>(reader: R, file_name: String, size: Option<u64>, mut progress: F) -> Part
+where
+    R: AsyncRead + Send + 'static,
+    F: FnMut(u64) + Send + 'static,
+{
+    let mut sent: u64 = 0;
+    let body = Body::wrap_stream(ReaderStream::new(reader).inspect_ok(move |chunk| {
+        sent = sent.saturating_add(chunk.len() as u64);
+        progress(sent);
+    }));
+
+    match size {
+        Some(size) => Part::stream_with_length(body, size),
+        None => Part::stream(body),
+    }
+    .file_name(file_name)
+}
+
+/// Return the file name of the given path, falling back to the given default.
+pub fn file_name(path: &Path, default: &str) -> String {
+    path.file_name().map_or_else(
+        || default.to_string(),
+        |file_name| file_name.to_string_lossy().into_owned(),
+    )
+}
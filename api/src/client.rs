@@ -1,31 +1,53 @@
 use reqwest::{Certificate, Url};
 
+pub use self::builder::ClientBuilder;
+use crate::RetryPolicy;
+
+mod builder;
+
 /// Mender server API client.
 #[derive(Clone, Debug)]
 pub struct Client {
     pub(crate) base_url: Url,
-    pub(crate) client: reqwest::Client,
+    pub(crate) http: reqwest::Client,
+    pub(crate) retry_policy: RetryPolicy,
 }
 
 impl Client {
     /// Crate a new API instance.
     ///
+    /// Use [`Client::builder`] for further connection settings.
+    ///
     /// # Errors
     ///
-    /// Returns a [`reqwest::Error`] if the client could not be built.
+    /// Returns a [`Error`](crate::Error) if the client could not be built.
     pub fn new(
         base_url: Url,
         certificate: Option<Certificate>,
         accept_invalid_certificates: bool,
-    ) -> reqwest::Result<Self> {
-        let mut builder = reqwest::Client::builder().use_rustls_tls();
+    ) -> crate::Result<Self> {
+        Self::builder(base_url)
+            .with_root_certificates(certificate)
+            .with_accept_invalid_certificates(accept_invalid_certificates)
+            .build()
+    }
 
-        if let Some(certificate) = certificate {
-            builder = builder
-                .add_root_certificate(certificate)
-                .danger_accept_invalid_certs(accept_invalid_certificates);
-        }
+    /// Create a builder for a client of the Mender server at the given base URL.
+    #[must_use]
+    pub fn builder(base_url: Url) -> ClientBuilder {
+        ClientBuilder::new(base_url)
+    }
+
+    /// Return the base URL of the Mender server.
+    #[must_use]
+    pub const fn base_url(&self) -> &Url {
+        &self.base_url
+    }
 
-        builder.build().map(|client| Self { base_url, client })
+    /// Set the policy for retrying failed requests of sessions created by this client.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
     }
 }
@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Identity, Proxy, Url};
+
+use crate::{Client, RetryPolicy};
+
+/// User agent sent unless another one is set.
+const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Builder of a [`Client`] with custom connection settings.
+#[derive(Debug)]
+pub struct ClientBuilder {
+    base_url: Url,
+    root_certificates: Vec<Certificate>,
+    identity: Option<Identity>,
+    accept_invalid_certificates: bool,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    user_agent: String,
+    headers: HeaderMap,
+    retry_policy: RetryPolicy,
+}
+
+impl ClientBuilder {
+    /// Creates a new `ClientBuilder` for the Mender server at the given base URL.
+    #[must_use]
+    pub fn new(base_url: Url) -> Self {
+        Self {
+            base_url,
+            root_certificates: Vec::new(),
+            identity: None,
+            accept_invalid_certificates: false,
+            connect_timeout: None,
+            timeout: None,
+            proxy: None,
+            user_agent: DEFAULT_USER_AGENT.to_owned(),
+            headers: HeaderMap::new(),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Add a trusted root certificate.
+    #[must_use]
+    pub fn with_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Add multiple trusted root certificates.
+    #[must_use]
+    pub fn with_root_certificates<I>(mut self, certificates: I) -> Self
+    where
+        I: IntoIterator<Item = Certificate>,
+    {
+        self.root_certificates.extend(certificates);
+        self
+    }
+
+    /// Set the client identity, i.e. certificate and private key, for mutual TLS.
+    #[must_use]
+    pub fn with_identity(mut self, identity: Identity) -> Self {
+        self.identity.replace(identity);
+        self
+    }
+
+    /// Set whether to accept invalid certificates (insecure).
+    #[must_use]
+    pub const fn with_accept_invalid_certificates(mut self, accept: bool) -> Self {
+        self.accept_invalid_certificates = accept;
+        self
+    }
+
+    /// Set the timeout for establishing connections.
+    #[must_use]
+    pub const fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout for each request, from connecting until the response body is read.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Send all requests through the given proxy.
+    ///
+    /// Without one, the proxies of the `HTTP_PROXY` and `HTTPS_PROXY` environment variables
+    /// are used.
+    #[must_use]
+    pub fn with_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy.replace(proxy);
+        self
+    }
+
+    /// Set the user agent.
+    #[must_use]
+    pub fn with_user_agent<T>(mut self, user_agent: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Add a header to send with every request.
+    #[must_use]
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Set the policy for retrying failed requests.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Build the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Error`](crate::Error) if the client could not be built,
+    /// e.g. because the identity is not supported by the TLS backend.
+    pub fn build(self) -> crate::Result<Client> {
+        let mut builder = reqwest::Client::builder()
+            .use_rustls_tls()
+            .danger_accept_invalid_certs(self.accept_invalid_certificates)
+            .user_agent(self.user_agent)
+            .default_headers(self.headers);
+
+        for certificate in self.root_certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+
+        if let Some(identity) = self.identity {
+            builder = builder.identity(identity);
+        }
+
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(proxy) = self.proxy {
+            builder = builder.proxy(proxy);
+        }
+
+        builder
+            .build()
+            .map(|http| Client {
+                base_url: self.base_url,
+                http,
+                retry_policy: self.retry_policy,
+            })
+            .map_err(crate::Error::Client)
+    }
+}
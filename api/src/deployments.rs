@@ -6,10 +6,13 @@ use tokio::task::JoinSet;
 use uuid::Uuid;
 
 use crate::Devices;
-use crate::dto::{DeploymentStatus, ListDeployment, NewDeployment, PutDeployment};
+use crate::dto::{
+    DeploymentDevice, DeploymentLog, DeploymentStatistics, DeploymentStatus, ListDeployment,
+    NewDeployment, PutDeployment, StatisticsResponse,
+};
 use crate::pagination::{DEFAULT_PAGE_SIZE, Pager, Pages, PaginatedIterator};
 use crate::session::Session;
-use crate::utils::ResponseExt;
+use crate::utils::{RequestBuilderExt, ResponseExt};
 
 const PATH: &str = "/api/management/v1/deployments/deployments";
 
@@ -25,13 +28,33 @@ pub trait Deployments {
     fn collect(
         &self,
         page_size: Option<NonZero<usize>>,
-    ) -> impl Future<Output = reqwest::Result<Vec<ListDeployment>>> + Send;
+    ) -> impl Future<Output = crate::Result<Vec<ListDeployment>>> + Send;
 
     /// Show a deployment.
-    fn show(&self, id: Uuid) -> impl Future<Output = reqwest::Result<ListDeployment>> + Send;
+    fn show(&self, id: Uuid) -> impl Future<Output = crate::Result<ListDeployment>> + Send;
 
     /// List device of the given deployment.
-    fn devices_of(&self, id: Uuid) -> impl Future<Output = reqwest::Result<Vec<Uuid>>> + Send;
+    fn devices_of(&self, id: Uuid) -> impl Future<Output = crate::Result<Vec<Uuid>>> + Send;
+
+    /// Get the number of devices per status of the given deployment.
+    fn statistics(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = crate::Result<DeploymentStatistics>> + Send;
+
+    /// List the deployment status of each device of the given deployment.
+    fn device_statuses(
+        &self,
+        id: Uuid,
+        page_size: Option<NonZero<usize>>,
+    ) -> impl Future<Output = crate::Result<Vec<DeploymentDevice>>> + Send;
+
+    /// Get the deployment log of a device of the given deployment.
+    fn device_log(
+        &self,
+        deployment_id: Uuid,
+        device_id: Uuid,
+    ) -> impl Future<Output = crate::Result<DeploymentLog>> + Send;
 
     /// Create a new deployment.
     fn create<N, A>(
@@ -40,11 +63,19 @@ pub trait Deployments {
         artifact_name: A,
         devices: &[Uuid],
         retries: usize,
-    ) -> impl Future<Output = reqwest::Result<()>> + Send
+    ) -> impl Future<Output = crate::Result<()>> + Send
     where
         N: AsRef<str> + Send + Sync,
         A: AsRef<str> + Send + Sync;
 
+    /// Create a new deployment from a deployment request.
+    ///
+    /// This allows phased and scheduled deployments, see [`NewDeployment`].
+    fn create_from(
+        &self,
+        deployment: &NewDeployment<'_, '_, '_>,
+    ) -> impl Future<Output = crate::Result<()>> + Send;
+
     /// Create a new deployment for a group of devices.
     fn create_for_group<N, A, G>(
         &self,
@@ -52,29 +83,65 @@ pub trait Deployments {
         artifact_name: A,
         group_name: G,
         retries: usize,
-    ) -> impl Future<Output = reqwest::Result<()>> + Send
+    ) -> impl Future<Output = crate::Result<()>> + Send
     where
         N: AsRef<str> + Send + Sync,
         A: AsRef<str> + Send + Sync,
         G: Display + Send + Sync;
 
+    /// Create a new deployment for a group of devices from a deployment request.
+    ///
+    /// The devices of the deployment request are ignored in favor of the group's devices.
+    fn create_for_group_from<G>(
+        &self,
+        group_name: G,
+        deployment: &NewDeployment<'_, '_, '_>,
+    ) -> impl Future<Output = crate::Result<()>> + Send
+    where
+        G: Display + Send + Sync;
+
+    /// Create a new deployment for all devices matching a saved inventory filter.
+    ///
+    /// Devices that match the filter after the deployment was created are picked up by the server
+    /// as long as the deployment's maximum number of devices has not been reached.
+    fn create_for_filter<N, A>(
+        &self,
+        name: N,
+        artifact_name: A,
+        filter_id: Uuid,
+        retries: usize,
+    ) -> impl Future<Output = crate::Result<()>> + Send
+    where
+        N: AsRef<str> + Send + Sync,
+        A: AsRef<str> + Send + Sync;
+
+    /// Create a new deployment for all devices matching a saved inventory filter from a deployment
+    /// request.
+    ///
+    /// The devices of the deployment request are ignored in favor of the filter's devices.
+    fn create_for_filter_from(
+        &self,
+        filter_id: Uuid,
+        deployment: &NewDeployment<'_, '_, '_>,
+    ) -> impl Future<Output = crate::Result<()>> + Send;
+
     /// Abort a deployment.
-    fn abort(&self, id: Uuid) -> impl Future<Output = reqwest::Result<()>> + Send;
+    fn abort(&self, id: Uuid) -> impl Future<Output = crate::Result<()>> + Send;
 
     /// Abort all ongoing deployments.
     fn abort_all(
         &self,
         page_size: Option<NonZero<usize>>,
-    ) -> impl Future<Output = reqwest::Result<()>> + Send;
+    ) -> impl Future<Output = crate::Result<()>> + Send;
 
     /// Abort a deployment for a given device.
-    fn abort_device(&self, device_id: Uuid) -> impl Future<Output = reqwest::Result<()>> + Send;
+    fn abort_device(&self, device_id: Uuid) -> impl Future<Output = crate::Result<()>> + Send;
 
     /// Abort all deployments by-device.
     fn abort_all_by_device(
         &self,
         page_size: Option<NonZero<usize>>,
-    ) -> impl Future<Output = reqwest::Result<()>> + Send;
+    ) -> impl Future<Output = crate::Result<()>> + Send;
 }
 
 impl Deployments for Session {
@@ -89,50 +156,102 @@ impl Deployments for Session {
     async fn collect(
         &self,
         page_size: Option<NonZero<usize>>,
-    ) -> reqwest::Result<Vec<ListDeployment>> {
+    ) -> crate::Result<Vec<ListDeployment>> {
         Pager::new(self, PATH.into(), page_size.unwrap_or(DEFAULT_PAGE_SIZE))
             .collect()
             .await
     }
 
-    async fn show(&self, id: Uuid) -> reqwest::Result<ListDeployment> {
+    async fn show(&self, id: Uuid) -> crate::Result<ListDeployment> {
         self.get(format!("{PATH}/{id}"), None)
-            .send()
+            .send_with(self)
+            .await?
+            .check_status()
             .await?
-            .error_for_status()?
-            .json()
+            .decode()
             .await
     }
 
-    async fn devices_of(&self, id: Uuid) -> reqwest::Result<Vec<Uuid>> {
+    async fn devices_of(&self, id: Uuid) -> crate::Result<Vec<Uuid>> {
         self.get(format!("{PATH}/{id}/device_list"), None)
-            .send()
+            .send_with(self)
             .await?
-            .error_for_status()?
-            .json()
+            .check_status()
+            .await?
+            .decode()
             .await
     }
 
+    async fn statistics(&self, id: Uuid) -> crate::Result<DeploymentStatistics> {
+        self.get(format!("{PATH}/{id}/statistics"), None)
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .decode::<StatisticsResponse>()
+            .await
+            .map(Into::into)
+    }
+
+    async fn device_statuses(
+        &self,
+        id: Uuid,
+        page_size: Option<NonZero<usize>>,
+    ) -> crate::Result<Vec<DeploymentDevice>> {
+        Pager::new(
+            self,
+            format!("{PATH}/{id}/devices/list").into(),
+            page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+        )
+        .collect()
+        .await
+    }
+
+    async fn device_log(
+        &self,
+        deployment_id: Uuid,
+        device_id: Uuid,
+    ) -> crate::Result<DeploymentLog> {
+        self.get(
+            format!("{PATH}/{deployment_id}/devices/{device_id}/log"),
+            None,
+        )
+        .send_with(self)
+        .await?
+        .check_status()
+        .await?
+        .text()
+        .await
+        .map(Into::into)
+        .map_err(Into::into)
+    }
+
     async fn create<N, A>(
         &self,
         name: N,
         artifact_name: A,
         devices: &[Uuid],
         retries: usize,
-    ) -> reqwest::Result<()>
+    ) -> crate::Result<()>
     where
         N: AsRef<str> + Send + Sync,
         A: AsRef<str> + Send + Sync,
     {
+        self.create_from(
+            &NewDeployment::new(name.as_ref(), artifact_name.as_ref())
+                .with_devices(devices)
+                .with_retries(retries),
+        )
+        .await
+    }
+
+    async fn create_from(&self, deployment: &NewDeployment<'_, '_, '_>) -> crate::Result<()> {
         self.post(PATH, None)
-            .json(
-                &NewDeployment::new(name.as_ref(), artifact_name.as_ref())
-                    .with_devices(devices)
-                    .with_retries(retries),
-            )
-            .send()
-            .await?
-            .error_for_status()?
+            .json(deployment)
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
             .ensure_empty()
             .await
     }
@@ -143,32 +262,82 @@ impl Deployments for Session {
         artifact_name: A,
         group_name: G,
         retries: usize,
-    ) -> reqwest::Result<()>
+    ) -> crate::Result<()>
     where
         N: AsRef<str> + Send + Sync,
         A: AsRef<str> + Send + Sync,
         G: Display + Send + Sync,
+    {
+        self.create_for_group_from(
+            group_name,
+            &NewDeployment::new(name.as_ref(), artifact_name.as_ref()).with_retries(retries),
+        )
+        .await
+    }
+
+    async fn create_for_group_from<G>(
+        &self,
+        group_name: G,
+        deployment: &NewDeployment<'_, '_, '_>,
+    ) -> crate::Result<()>
+    where
+        G: Display + Send + Sync,
     {
         self.post(format!("{PATH}/group/{group_name}"), None)
-            .json(&NewDeployment::new(name.as_ref(), artifact_name.as_ref()).with_retries(retries))
-            .send()
+            .json(deployment)
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .ensure_empty()
+            .await
+    }
+
+    async fn create_for_filter<N, A>(
+        &self,
+        name: N,
+        artifact_name: A,
+        filter_id: Uuid,
+        retries: usize,
+    ) -> crate::Result<()>
+    where
+        N: AsRef<str> + Send + Sync,
+        A: AsRef<str> + Send + Sync,
+    {
+        self.create_for_filter_from(
+            filter_id,
+            &NewDeployment::new(name.as_ref(), artifact_name.as_ref()).with_retries(retries),
+        )
+        .await
+    }
+
+    async fn create_for_filter_from(
+        &self,
+        filter_id: Uuid,
+        deployment: &NewDeployment<'_, '_, '_>,
+    ) -> crate::Result<()> {
+        self.post(format!("{PATH}/filter"), None)
+            .json(&deployment.clone().with_filter_id(filter_id))
+            .send_with(self)
+            .await?
+            .check_status()
             .await?
-            .error_for_status()?
             .ensure_empty()
             .await
     }
 
-    async fn abort(&self, id: Uuid) -> reqwest::Result<()> {
+    async fn abort(&self, id: Uuid) -> crate::Result<()> {
         self.put(format!("{PATH}/{id}/status"), None)
             .json(&PutDeployment::new(DeploymentStatus::Aborted))
-            .send()
+            .send_with(self)
+            .await?
+            .check_status()
             .await?
-            .error_for_status()?
             .ensure_empty()
             .await
     }
 
-    async fn abort_all(&self, page_size: Option<NonZero<usize>>) -> reqwest::Result<()> {
+    async fn abort_all(&self, page_size: Option<NonZero<usize>>) -> crate::Result<()> {
         let mut pages = Deployments::pages(self, page_size);
 
         while let Some(page) = pages.next().await {
@@ -195,16 +364,17 @@ impl Deployments for Session {
         Ok(())
     }
 
-    async fn abort_device(&self, device_id: Uuid) -> reqwest::Result<()> {
+    async fn abort_device(&self, device_id: Uuid) -> crate::Result<()> {
         self.delete(format!("{PATH}/devices/{device_id}"), None)
-            .send()
+            .send_with(self)
+            .await?
+            .check_status()
             .await?
-            .error_for_status()?
             .ensure_empty()
             .await
     }
 
-    async fn abort_all_by_device(&self, page_size: Option<NonZero<usize>>) -> reqwest::Result<()> {
+    async fn abort_all_by_device(&self, page_size: Option<NonZero<usize>>) -> crate::Result<()> {
         let mut pages = Devices::pages(self, page_size);
 
         while let Some(page) = pages.next().await {
@@ -0,0 +1,194 @@
+//! Device authentication management API.
+
+use std::num::NonZero;
+
+use uuid::Uuid;
+
+use crate::dto::{AuthDevice, Identity, Preauthorization, PutAuthSet, Status};
+use crate::pagination::{DEFAULT_PAGE_SIZE, Pager, Pages, PaginatedIterator};
+use crate::session::Session;
+use crate::utils::{RequestBuilderExt, ResponseExt};
+
+const PATH: &str = "/api/management/v2/devauth/devices";
+
+/// Device authentication management API.
+pub trait DeviceAuth {
+    /// Iterate over pages of devices, optionally filtered by their authentication status.
+    fn pages(
+        &self,
+        status: Option<Status>,
+        page_size: Option<NonZero<usize>>,
+    ) -> Pages<'_, '_, AuthDevice>;
+
+    /// List devices, optionally filtered by their authentication status.
+    fn list(
+        &self,
+        status: Option<Status>,
+        page_size: Option<NonZero<usize>>,
+    ) -> PaginatedIterator<'_, '_, AuthDevice>;
+
+    /// Collect devices, optionally filtered by their authentication status, into a `Vec`.
+    fn collect(
+        &self,
+        status: Option<Status>,
+        page_size: Option<NonZero<usize>>,
+    ) -> impl Future<Output = crate::Result<Vec<AuthDevice>>> + Send;
+
+    /// Get a specific device along with its authentication sets.
+    fn get(&self, id: Uuid) -> impl Future<Output = crate::Result<AuthDevice>> + Send;
+
+    /// Set the status of an authentication set of a device.
+    fn set_status(
+        &self,
+        device_id: Uuid,
+        auth_set_id: Uuid,
+        status: Status,
+    ) -> impl Future<Output = crate::Result<()>> + Send;
+
+    /// Accept an authentication set of a device.
+    fn accept(
+        &self,
+        device_id: Uuid,
+        auth_set_id: Uuid,
+    ) -> impl Future<Output = crate::Result<()>> + Send;
+
+    /// Reject an authentication set of a device.
+    fn reject(
+        &self,
+        device_id: Uuid,
+        auth_set_id: Uuid,
+    ) -> impl Future<Output = crate::Result<()>> + Send;
+
+    /// Dismiss, i.e. remove, an authentication set of a device.
+    fn dismiss(
+        &self,
+        device_id: Uuid,
+        auth_set_id: Uuid,
+    ) -> impl Future<Output = crate::Result<()>> + Send;
+
+    /// Decommission a device.
+    fn decommission(&self, device_id: Uuid) -> impl Future<Output = crate::Result<()>> + Send;
+
+    /// Preauthorize a device identity with the given public key.
+    fn preauthorize<K>(
+        &self,
+        identity: Identity,
+        pubkey: K,
+        force: bool,
+    ) -> impl Future<Output = crate::Result<()>> + Send
+    where
+        K: AsRef<str> + Send + Sync;
+}
+
+impl DeviceAuth for Session {
+    fn pages(
+        &self,
+        status: Option<Status>,
+        page_size: Option<NonZero<usize>>,
+    ) -> Pages<'_, '_, AuthDevice> {
+        pager(self, status, page_size).into()
+    }
+
+    fn list(
+        &self,
+        status: Option<Status>,
+        page_size: Option<NonZero<usize>>,
+    ) -> PaginatedIterator<'_, '_, AuthDevice> {
+        pager(self, status, page_size).into()
+    }
+
+    async fn collect(
+        &self,
+        status: Option<Status>,
+        page_size: Option<NonZero<usize>>,
+    ) -> crate::Result<Vec<AuthDevice>> {
+        pager(self, status, page_size).collect().await
+    }
+
+    async fn get(&self, id: Uuid) -> crate::Result<AuthDevice> {
+        self.get(format!("{PATH}/{id}"), None)
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .decode()
+            .await
+    }
+
+    async fn set_status(
+        &self,
+        device_id: Uuid,
+        auth_set_id: Uuid,
+        status: Status,
+    ) -> crate::Result<()> {
+        self.put(
+            format!("{PATH}/{device_id}/auth/{auth_set_id}/status"),
+            None,
+        )
+        .json(&PutAuthSet::new(status))
+        .send_with(self)
+        .await?
+        .check_status()
+        .await?
+        .ensure_empty()
+        .await
+    }
+
+    async fn accept(&self, device_id: Uuid, auth_set_id: Uuid) -> crate::Result<()> {
+        self.set_status(device_id, auth_set_id, Status::Accepted)
+            .await
+    }
+
+    async fn reject(&self, device_id: Uuid, auth_set_id: Uuid) -> crate::Result<()> {
+        self.set_status(device_id, auth_set_id, Status::Rejected)
+            .await
+    }
+
+    async fn dismiss(&self, device_id: Uuid, auth_set_id: Uuid) -> crate::Result<()> {
+        self.delete(format!("{PATH}/{device_id}/auth/{auth_set_id}"), None)
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .ensure_empty()
+            .await
+    }
+
+    async fn decommission(&self, device_id: Uuid) -> crate::Result<()> {
+        self.delete(format!("{PATH}/{device_id}"), None)
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .ensure_empty()
+            .await
+    }
+
+    async fn preauthorize<K>(&self, identity: Identity, pubkey: K, force: bool) -> crate::Result<()>
+    where
+        K: AsRef<str> + Send + Sync,
+    {
+        self.post(PATH, None)
+            .json(&Preauthorization::new(identity, pubkey.as_ref()).with_force(force))
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .ensure_empty()
+            .await
+    }
+}
+
+/// Create a pager over devices, optionally filtered by their authentication status.
+fn pager(
+    session: &Session,
+    status: Option<Status>,
+    page_size: Option<NonZero<usize>>,
+) -> Pager<'_, 'static> {
+    let pager = Pager::new(session, PATH.into(), page_size.unwrap_or(DEFAULT_PAGE_SIZE));
+
+    match status {
+        Some(status) => pager.with_query(format!("status={status}").into()),
+        None => pager,
+    }
+}
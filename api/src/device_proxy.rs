@@ -23,8 +23,8 @@ impl DeviceProxy<'_> {
     ///
     /// # Errors
     ///
-    /// Return a [`reqwest::Error`] if the request fails.
-    pub async fn get(&self) -> reqwest::Result<Device> {
+    /// Return a [`Error`](crate::Error) if the request fails.
+    pub async fn get(&self) -> crate::Result<Device> {
         Devices::get(self.session, self.id).await
     }
 
@@ -32,8 +32,8 @@ impl DeviceProxy<'_> {
     ///
     /// # Errors
     ///
-    /// Return a [`reqwest::Error`] if the request fails.
-    pub async fn get_group(&self) -> reqwest::Result<DeviceGroup> {
+    /// Return a [`Error`](crate::Error) if the request fails.
+    pub async fn get_group(&self) -> crate::Result<DeviceGroup> {
         Devices::get_group(self.session, self.id).await
     }
 
@@ -41,8 +41,8 @@ impl DeviceProxy<'_> {
     ///
     /// # Errors
     ///
-    /// Return a [`reqwest::Error`] if the request fails.
-    pub async fn add_to_group<T>(&self, group_name: T) -> reqwest::Result<()>
+    /// Return a [`Error`](crate::Error) if the request fails.
+    pub async fn add_to_group<T>(&self, group_name: T) -> crate::Result<()>
     where
         T: ToString + Send,
     {
@@ -53,8 +53,8 @@ impl DeviceProxy<'_> {
     ///
     /// # Errors
     ///
-    /// Return a [`reqwest::Error`] if the request fails.
-    pub async fn add_tags(&self, tags: &[Tag]) -> reqwest::Result<()> {
+    /// Return a [`Error`](crate::Error) if the request fails.
+    pub async fn add_tags(&self, tags: &[Tag]) -> crate::Result<()> {
         Tags::add(self.session, self.id, tags).await
     }
 
@@ -62,8 +62,8 @@ impl DeviceProxy<'_> {
     ///
     /// # Errors
     ///
-    /// Return a [`reqwest::Error`] if the request fails.
-    pub async fn add_tag(&self, tag: Tag) -> reqwest::Result<()> {
+    /// Return a [`Error`](crate::Error) if the request fails.
+    pub async fn add_tag(&self, tag: Tag) -> crate::Result<()> {
         self.add_tags(&[tag]).await
     }
 
@@ -71,8 +71,8 @@ impl DeviceProxy<'_> {
     ///
     /// # Errors
     ///
-    /// Return a [`reqwest::Error`] if the request fails.
-    pub async fn assign_tags(&self, tags: &[Tag]) -> reqwest::Result<()> {
+    /// Return a [`Error`](crate::Error) if the request fails.
+    pub async fn assign_tags(&self, tags: &[Tag]) -> crate::Result<()> {
         Tags::assign(self.session, self.id, tags).await
     }
 
@@ -80,8 +80,8 @@ impl DeviceProxy<'_> {
     ///
     /// # Errors
     ///
-    /// Return a [`reqwest::Error`] if the request fails.
-    pub async fn clear_tags(&self) -> reqwest::Result<()> {
+    /// Return a [`Error`](crate::Error) if the request fails.
+    pub async fn clear_tags(&self) -> crate::Result<()> {
         Tags::clear(self.session, self.id).await
     }
 
@@ -89,8 +89,8 @@ impl DeviceProxy<'_> {
     ///
     /// # Errors
     ///
-    /// Return a [`reqwest::Error`] if the request fails.
-    pub async fn abort_deployment(&self) -> reqwest::Result<()> {
+    /// Return a [`Error`](crate::Error) if the request fails.
+    pub async fn abort_deployment(&self) -> crate::Result<()> {
         Deployments::abort_device(self.session, self.id).await
     }
 
@@ -98,8 +98,8 @@ impl DeviceProxy<'_> {
     ///
     /// # Errors
     ///
-    /// Return a [`reqwest::Error`] if the request fails.
-    pub async fn tags(&self) -> reqwest::Result<Vec<Attribute>> {
+    /// Return a [`Error`](crate::Error) if the request fails.
+    pub async fn tags(&self) -> crate::Result<Vec<Attribute>> {
         Ok(self.get().await?.tags().cloned().collect())
     }
 }
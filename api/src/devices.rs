@@ -8,7 +8,7 @@ use crate::device_proxy::DeviceProxy;
 use crate::dto::{Device, DeviceGroup};
 use crate::pagination::{DEFAULT_PAGE_SIZE, Pager, Pages, PaginatedIterator};
 use crate::session::Session;
-use crate::utils::ResponseExt;
+use crate::utils::{RequestBuilderExt, ResponseExt};
 
 const PATH: &str = "/api/management/v1/inventory/devices";
 
@@ -24,20 +24,20 @@ pub trait Devices {
     fn collect(
         &self,
         page_size: Option<NonZero<usize>>,
-    ) -> impl Future<Output = reqwest::Result<Vec<Device>>> + Send;
+    ) -> impl Future<Output = crate::Result<Vec<Device>>> + Send;
 
     /// Get a specific device by its ID.
-    fn get(&self, id: Uuid) -> impl Future<Output = reqwest::Result<Device>> + Send;
+    fn get(&self, id: Uuid) -> impl Future<Output = crate::Result<Device>> + Send;
 
     /// Get the group of the specified device.
-    fn get_group(&self, id: Uuid) -> impl Future<Output = reqwest::Result<DeviceGroup>> + Send;
+    fn get_group(&self, id: Uuid) -> impl Future<Output = crate::Result<DeviceGroup>> + Send;
 
     /// Add the device to the specified group.
     fn set_group<T>(
         &self,
         id: Uuid,
         group_name: T,
-    ) -> impl Future<Output = reqwest::Result<()>> + Send
+    ) -> impl Future<Output = crate::Result<()>> + Send
     where
         T: ToString + Send;
 
@@ -54,39 +54,42 @@ impl Devices for Session {
         Pager::new(self, PATH.into(), page_size.unwrap_or(DEFAULT_PAGE_SIZE)).into()
     }
 
-    async fn collect(&self, page_size: Option<NonZero<usize>>) -> reqwest::Result<Vec<Device>> {
+    async fn collect(&self, page_size: Option<NonZero<usize>>) -> crate::Result<Vec<Device>> {
         Pager::new(self, PATH.into(), page_size.unwrap_or(DEFAULT_PAGE_SIZE))
             .collect()
             .await
     }
 
-    async fn get(&self, id: Uuid) -> reqwest::Result<Device> {
+    async fn get(&self, id: Uuid) -> crate::Result<Device> {
         self.get(format!("{PATH}/{id}"), None)
-            .send()
+            .send_with(self)
             .await?
-            .error_for_status()?
-            .json()
+            .check_status()
+            .await?
+            .decode()
             .await
     }
 
-    async fn get_group(&self, id: Uuid) -> reqwest::Result<DeviceGroup> {
+    async fn get_group(&self, id: Uuid) -> crate::Result<DeviceGroup> {
         self.get(format!("{PATH}/{id}/group"), None)
-            .send()
+            .send_with(self)
+            .await?
+            .check_status()
             .await?
-            .error_for_status()?
-            .json()
+            .decode()
             .await
     }
 
-    async fn set_group<T>(&self, id: Uuid, group_name: T) -> reqwest::Result<()>
+    async fn set_group<T>(&self, id: Uuid, group_name: T) -> crate::Result<()>
     where
         T: ToString + Send,
     {
         self.put(format!("{PATH}/{id}/group"), None)
             .json(&DeviceGroup::new(group_name.to_string()))
-            .send()
+            .send_with(self)
+            .await?
+            .check_status()
             .await?
-            .error_for_status()?
             .ensure_empty()
             .await
     }
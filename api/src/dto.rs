@@ -1,21 +1,38 @@
 //! Mender API data transfer objects (DTOs).
 
-pub use self::artifact::{Artifact, Dependency, File, Info, TypeInfo, Update};
-pub use self::attribute::{Attribute, KnownAttribute, UnknownAttribute};
+pub use self::artifact::{Artifact, Dependency, DownloadLink, File, Info, TypeInfo, Update};
+pub use self::attribute::{Attribute, KnownAttribute, UnknownAttribute, VALUE_SEPARATOR};
 pub use self::authentification_set::AuthentificationSet;
 pub use self::bootloader_integration::BootloaderIntegration;
 pub use self::country::Country;
+pub(crate) use self::deployment::StatisticsResponse;
+pub use self::deployment::device::Device as DeploymentDevice;
 pub use self::deployment::list::Deployment as ListDeployment;
+pub use self::deployment::log::{Entry as DeploymentLogEntry, Log as DeploymentLog};
 pub use self::deployment::new::Deployment as NewDeployment;
 pub use self::deployment::put::Deployment as PutDeployment;
-pub use self::deployment::{Kind, Status as DeploymentStatus};
+pub use self::deployment::{
+    DeviceStatus as DeviceDeploymentStatus, Kind, ParsePhaseError, Phase,
+    Statistics as DeploymentStatistics, Status as DeploymentStatus,
+};
+pub use self::devauth::device::Device as AuthDevice;
+pub use self::devauth::preauthorization::Preauthorization;
+pub use self::devauth::put::AuthSet as PutAuthSet;
 pub use self::device::{Device, Group as DeviceGroup};
 pub use self::device_type::DeviceType;
+pub use self::filter::Filter;
+pub use self::filter::new::Filter as NewFilter;
 pub use self::group::PatchGroupResponse;
 pub use self::identity::Identity;
+pub use self::personal_access_token::PersonalAccessToken;
+pub use self::personal_access_token::new::PersonalAccessToken as NewPersonalAccessToken;
 pub use self::release::Release;
 pub use self::rootfs_type::RootfsType;
 pub use self::scope::Scope;
+pub use self::search::{
+    Operator, Order as SortOrder, ParsePredicateError, Predicate, Search,
+    Selection as AttributeSelection, Sort,
+};
 pub use self::status::Status;
 pub use self::tag::Tag;
 
@@ -25,13 +42,17 @@ mod authentification_set;
 mod bootloader_integration;
 mod country;
 mod deployment;
+mod devauth;
 mod device;
 mod device_type;
+mod filter;
 mod group;
 mod identity;
+mod personal_access_token;
 mod release;
 mod rootfs_type;
 mod scope;
+mod search;
 mod status;
 mod tag;
 mod types;
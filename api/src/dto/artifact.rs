@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 pub use self::dependency::Dependency;
+pub use self::download_link::DownloadLink;
 pub use self::file::File;
 pub use self::info::Info;
 pub use self::type_info::TypeInfo;
@@ -13,6 +14,7 @@ pub use self::update::Update;
 use crate::dto::DeviceType;
 
 mod dependency;
+mod download_link;
 mod file;
 mod info;
 mod type_info;
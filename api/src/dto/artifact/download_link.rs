@@ -0,0 +1,24 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+/// A time-limited link to download an artifact.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DownloadLink {
+    uri: String,
+    expire: DateTime<FixedOffset>,
+}
+
+impl DownloadLink {
+    /// Returns the URI to download the artifact from.
+    #[must_use]
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Returns the time at which the link expires.
+    #[must_use]
+    pub const fn expire(&self) -> DateTime<FixedOffset> {
+        self.expire
+    }
+}
@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
-pub use self::known_attribute::KnownAttribute;
+pub use self::known_attribute::{KnownAttribute, VALUE_SEPARATOR};
 pub use self::unknown_attribute::UnknownAttribute;
 use crate::dto::scope::Scope;
 
@@ -28,6 +28,24 @@ impl Attribute {
             Self::Unknown(unknown) => unknown.scope(),
         }
     }
+
+    /// Return the attribute's name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Known(known) => known.name(),
+            Self::Unknown(unknown) => unknown.name(),
+        }
+    }
+
+    /// Return the attribute's value in its canonical string representation.
+    #[must_use]
+    pub fn value_to_string(&self) -> String {
+        match self {
+            Self::Known(known) => known.value_to_string(),
+            Self::Unknown(unknown) => unknown.value().to_string(),
+        }
+    }
 }
 
 impl Display for Attribute {
@@ -11,7 +11,10 @@ use crate::dto::scope::Scope;
 use crate::dto::status::Status;
 use crate::dto::types::OneOrMany;
 use crate::dto::{BootloaderIntegration, Country, DeviceType, RootfsType};
-use crate::utils::{DisplaySlice, as_str};
+use crate::utils::{DisplaySlice, as_str, join};
+
+/// Separator for joining multiple values of an attribute.
+pub const VALUE_SEPARATOR: &str = ", ";
 
 /// Known attributes for device in the Mender inventory API.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
@@ -281,6 +284,76 @@ impl KnownAttribute {
             | Self::UpdateModules { scope, .. } => *scope,
         }
     }
+
+    /// Return the attribute's name as used by the API.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Name { .. } => "name",
+            Self::Mac { .. } => "mac",
+            Self::Created { .. } => "created_ts",
+            Self::Updated { .. } => "updated_ts",
+            Self::Status { .. } => "status",
+            Self::Group { .. } => "group",
+            Self::ArtifactName { .. } => "artifact_name",
+            Self::CpuModel { .. } => "cpu_model",
+            Self::DeviceType { .. } => "device_type",
+            Self::Hostname { .. } => "hostname",
+            Self::Ipv4Eth0 { .. } => "ipv4_eth0",
+            Self::Ipv6Eth0 { .. } => "ipv6_eth0",
+            Self::Kernel { .. } => "kernel",
+            Self::MacEth0 { .. } => "mac_eth0",
+            Self::MemTotalKB { .. } => "mem_total_kB",
+            Self::MenderBootloaderIntegration { .. } => "mender_bootloader_integration",
+            Self::MenderClientVersion { .. } => "mender_client_version",
+            Self::NetworkInterfaces { .. } => "network_interfaces",
+            Self::Os { .. } => "os",
+            Self::RootfsType { .. } => "rootfs_type",
+            Self::GeoCity { .. } => "geo-city",
+            Self::GeoCountry { .. } => "geo-country",
+            Self::GeoIp { .. } => "geo-ip",
+            Self::GeoTimezone { .. } => "geo-timezone",
+            Self::MacSit0 { .. } => "mac_sit0",
+            Self::RootfsImageChecksum { .. } => "rootfs-image.checksum",
+            Self::RootfsImageVersion { .. } => "rootfs-image.version",
+            Self::UpdateModules { .. } => "update_modules",
+        }
+    }
+
+    /// Return the attribute's value in its canonical string representation.
+    ///
+    /// Multiple values are joined by [`VALUE_SEPARATOR`], i.e. a comma followed by a space.
+    #[must_use]
+    pub fn value_to_string(&self) -> String {
+        match self {
+            Self::Name { value, .. }
+            | Self::Group { value, .. }
+            | Self::ArtifactName { value, .. }
+            | Self::CpuModel { value, .. }
+            | Self::Hostname { value, .. }
+            | Self::Kernel { value, .. }
+            | Self::Os { value, .. }
+            | Self::GeoCity { value, .. }
+            | Self::GeoTimezone { value, .. }
+            | Self::MacSit0 { value, .. }
+            | Self::RootfsImageChecksum { value, .. }
+            | Self::RootfsImageVersion { value, .. } => value.clone(),
+            Self::Mac { value, .. } | Self::MacEth0 { value, .. } => value.to_string(),
+            Self::Created { value, .. } | Self::Updated { value, .. } => value.to_rfc3339(),
+            Self::Status { value, .. } => value.to_string(),
+            Self::DeviceType { value, .. } => value.to_string(),
+            Self::Ipv4Eth0 { value, .. } => value.join(VALUE_SEPARATOR),
+            Self::Ipv6Eth0 { value, .. } => value.join(VALUE_SEPARATOR),
+            Self::MemTotalKB { value, .. } => value.to_string(),
+            Self::MenderBootloaderIntegration { value, .. } => value.to_string(),
+            Self::MenderClientVersion { value, .. } => value.to_string(),
+            Self::NetworkInterfaces { value, .. } => value.join(VALUE_SEPARATOR),
+            Self::RootfsType { value, .. } => value.to_string(),
+            Self::GeoCountry { value, .. } => value.to_string(),
+            Self::GeoIp { value, .. } => value.to_string(),
+            Self::UpdateModules { value, .. } => join(value, VALUE_SEPARATOR),
+        }
+    }
 }
 
 impl Display for KnownAttribute {
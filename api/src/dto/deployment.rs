@@ -1,10 +1,18 @@
 //! Deployment-related data structures and types.
 
+pub use self::device_status::DeviceStatus;
 pub use self::kind::Kind;
+pub use self::phase::{ParsePhaseError, Phase};
+pub use self::statistics::{Response as StatisticsResponse, Statistics};
 pub use self::status::Status;
 
+pub mod device;
+mod device_status;
 mod kind;
 pub mod list;
+pub mod log;
 pub mod new;
+mod phase;
 pub mod put;
+mod statistics;
 mod status;
@@ -0,0 +1,137 @@
+//! Deployment state of a single device.
+
+use std::fmt::Display;
+
+use chrono::{DateTime, FixedOffset};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::dto::deployment::DeviceStatus;
+
+/// The state of a deployment on a single device.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Device {
+    id: Uuid,
+    status: DeviceStatus,
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    substate: Option<String>,
+    created: DateTime<FixedOffset>,
+    #[serde(default)]
+    started: Option<DateTime<FixedOffset>>,
+    #[serde(default)]
+    finished: Option<DateTime<FixedOffset>>,
+    #[serde(default)]
+    deleted: Option<DateTime<FixedOffset>>,
+    #[serde(default, rename = "device_type")]
+    typ: Option<String>,
+    #[serde(rename = "log")]
+    log_available: bool,
+    #[serde(default)]
+    attempts: usize,
+    #[serde(default)]
+    retries: usize,
+    #[serde(default)]
+    image: Option<Map<String, Value>>,
+}
+
+impl Device {
+    /// Returns the ID of the device.
+    #[must_use]
+    pub const fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Returns the deployment status of the device.
+    #[must_use]
+    pub const fn status(&self) -> DeviceStatus {
+        self.status
+    }
+
+    /// Returns the state reported by the device, if any.
+    #[must_use]
+    pub fn state(&self) -> Option<&str> {
+        self.state.as_deref()
+    }
+
+    /// Returns the substate reported by the device, if any.
+    #[must_use]
+    pub fn substate(&self) -> Option<&str> {
+        self.substate.as_deref()
+    }
+
+    /// Returns the time the device was added to the deployment.
+    #[must_use]
+    pub const fn created(&self) -> DateTime<FixedOffset> {
+        self.created
+    }
+
+    /// Returns the time the device started the deployment, if it did.
+    #[must_use]
+    pub const fn started(&self) -> Option<DateTime<FixedOffset>> {
+        self.started
+    }
+
+    /// Returns the time the device finished the deployment, if it did.
+    #[must_use]
+    pub const fn finished(&self) -> Option<DateTime<FixedOffset>> {
+        self.finished
+    }
+
+    /// Returns the time the device was removed from the deployment, if it was.
+    #[must_use]
+    pub const fn deleted(&self) -> Option<DateTime<FixedOffset>> {
+        self.deleted
+    }
+
+    /// Returns the device type of the device, if known.
+    #[must_use]
+    pub fn device_type(&self) -> Option<&str> {
+        self.typ.as_deref()
+    }
+
+    /// Returns whether a deployment log is available for the device.
+    #[must_use]
+    pub const fn log_available(&self) -> bool {
+        self.log_available
+    }
+
+    /// Returns the number of attempts the device made to apply the deployment.
+    #[must_use]
+    pub const fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// Returns the number of retries allowed for the device.
+    #[must_use]
+    pub const fn retries(&self) -> usize {
+        self.retries
+    }
+
+    /// Returns the artifact assigned to the device, as reported by the server.
+    #[must_use]
+    pub const fn image(&self) -> Option<&Map<String, Value>> {
+        self.image.as_ref()
+    }
+}
+
+impl Display for Device {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} [{}", self.id, self.status)?;
+
+        if let Some(substate) = &self.substate {
+            write!(f, ": {substate}")?;
+        }
+
+        write!(f, "] attempts: {}/{}", self.attempts, self.retries)?;
+
+        if let Some(finished) = self.finished {
+            write!(f, ", finished: {finished}")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Possible deployment status of a single device.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub enum DeviceStatus {
+    /// The deployment has not yet started on the device.
+    #[serde(rename = "pending")]
+    Pending,
+    /// The device is downloading the artifact.
+    #[serde(rename = "downloading")]
+    Downloading,
+    /// The device is paused before installing the artifact.
+    #[serde(rename = "pause_before_installing")]
+    PauseBeforeInstalling,
+    /// The device is installing the artifact.
+    #[serde(rename = "installing")]
+    Installing,
+    /// The device is paused before rebooting.
+    #[serde(rename = "pause_before_rebooting")]
+    PauseBeforeRebooting,
+    /// The device is rebooting.
+    #[serde(rename = "rebooting")]
+    Rebooting,
+    /// The device is paused before committing the update.
+    #[serde(rename = "pause_before_committing")]
+    PauseBeforeCommitting,
+    /// The deployment succeeded on the device.
+    #[serde(rename = "success")]
+    Success,
+    /// The deployment failed on the device.
+    #[serde(rename = "failure")]
+    Failure,
+    /// No compatible artifact was found for the device.
+    #[serde(rename = "noartifact")]
+    NoArtifact,
+    /// The artifact was already installed on the device.
+    #[serde(rename = "already-installed")]
+    AlreadyInstalled,
+    /// The deployment was aborted on the device.
+    #[serde(rename = "aborted")]
+    Aborted,
+    /// The device was decommissioned during the deployment.
+    #[serde(rename = "decommissioned")]
+    Decommissioned,
+}
+
+impl DeviceStatus {
+    /// Return a static string representation of the status.
+    #[must_use]
+    pub const fn to_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Downloading => "downloading",
+            Self::PauseBeforeInstalling => "pause_before_installing",
+            Self::Installing => "installing",
+            Self::PauseBeforeRebooting => "pause_before_rebooting",
+            Self::Rebooting => "rebooting",
+            Self::PauseBeforeCommitting => "pause_before_committing",
+            Self::Success => "success",
+            Self::Failure => "failure",
+            Self::NoArtifact => "noartifact",
+            Self::AlreadyInstalled => "already-installed",
+            Self::Aborted => "aborted",
+            Self::Decommissioned => "decommissioned",
+        }
+    }
+
+    /// Returns whether the deployment on the device has reached a final state.
+    #[must_use]
+    pub const fn is_finished(self) -> bool {
+        matches!(
+            self,
+            Self::Success
+                | Self::Failure
+                | Self::NoArtifact
+                | Self::AlreadyInstalled
+                | Self::Aborted
+                | Self::Decommissioned
+        )
+    }
+}
+
+impl Display for DeviceStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+#[cfg(feature = "clap")]
+impl clap::ValueEnum for DeviceStatus {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Self::Pending,
+            Self::Downloading,
+            Self::PauseBeforeInstalling,
+            Self::Installing,
+            Self::PauseBeforeRebooting,
+            Self::Rebooting,
+            Self::PauseBeforeCommitting,
+            Self::Success,
+            Self::Failure,
+            Self::NoArtifact,
+            Self::AlreadyInstalled,
+            Self::Aborted,
+            Self::Decommissioned,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(clap::builder::PossibleValue::new(self.to_str()))
+    }
+}
@@ -0,0 +1,201 @@
+//! Deployment logs of a single device.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset};
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f %z";
+
+/// The deployment log of a single device.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Log {
+    text: String,
+    entries: Vec<Entry>,
+}
+
+impl Log {
+    /// Returns the raw text of the log.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the parsed log entries.
+    #[must_use]
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+}
+
+impl From<String> for Log {
+    /// Parse the log text as returned by the server.
+    ///
+    /// Lines that do not start with a timestamp are considered continuations of the previous
+    /// entry's message. Such lines preceding the first entry are only retained in the raw text.
+    fn from(text: String) -> Self {
+        let mut entries: Vec<Entry> = Vec::new();
+
+        for line in text.lines() {
+            if let Ok(entry) = line.parse() {
+                entries.push(entry);
+            } else if let Some(entry) = entries.last_mut() {
+                entry.message.push('\n');
+                entry.message.push_str(line);
+            }
+        }
+
+        Self { text, entries }
+    }
+}
+
+impl Display for Log {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// A single entry of a deployment log.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Entry {
+    timestamp: DateTime<FixedOffset>,
+    level: String,
+    message: String,
+}
+
+impl Entry {
+    /// Returns the timestamp of the entry.
+    #[must_use]
+    pub const fn timestamp(&self) -> DateTime<FixedOffset> {
+        self.timestamp
+    }
+
+    /// Returns the log level of the entry.
+    #[must_use]
+    pub fn level(&self) -> &str {
+        &self.level
+    }
+
+    /// Returns the message of the entry.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl FromStr for Entry {
+    type Err = chrono::ParseError;
+
+    /// Parse a log line of the form `2006-01-02 15:04:05.999999999 -0700 MST level: message`.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut fields = line.splitn(5, ' ');
+        let timestamp = fields.by_ref().take(3).collect::<Vec<_>>().join(" ");
+        let timestamp = DateTime::parse_from_str(&timestamp, TIMESTAMP_FORMAT)?;
+        let _zone = fields.next();
+        let rest = fields.next().unwrap_or_default();
+        let (level, message) = rest
+            .split_once(": ")
+            .or_else(|| rest.strip_suffix(':').map(|level| (level, "")))
+            .unwrap_or(("", rest));
+
+        Ok(Self {
+            timestamp,
+            level: level.to_string(),
+            message: message.to_string(),
+        })
+    }
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}: {}", self.timestamp, self.level, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::{Entry, Log};
+
+    const LOG: &str = "\
+2024-01-15 10:23:45.123456789 +0000 UTC info: Running Mender client version: 3.5.0
+2024-01-15 10:23:46.5 +0000 UTC error: Artifact install failed: Payload: can not install Payload: rootfs.ext4: exit status 1
+stderr: No space left on device
+2024-01-15 10:23:47 +0100 CET debug:
+";
+
+    #[test]
+    fn parses_entry() {
+        let entry: Entry =
+            "2024-01-15 10:23:45.123456789 +0000 UTC info: Running Mender client version: 3.5.0"
+                .parse()
+                .expect("line should parse");
+
+        assert_eq!(
+            entry.timestamp(),
+            DateTime::parse_from_rfc3339("2024-01-15T10:23:45.123456789Z").expect("valid date")
+        );
+        assert_eq!(entry.level(), "info");
+        assert_eq!(entry.message(), "Running Mender client version: 3.5.0");
+    }
+
+    #[test]
+    fn parses_entry_without_message() {
+        let entry: Entry = "2024-01-15 10:23:47 +0100 CET debug:"
+            .parse()
+            .expect("line should parse");
+
+        assert_eq!(entry.level(), "debug");
+        assert_eq!(entry.message(), "");
+    }
+
+    #[test]
+    fn parses_entry_without_level() {
+        let entry: Entry = "2024-01-15 10:23:47 +0100 CET plain text"
+            .parse()
+            .expect("line should parse");
+
+        assert_eq!(entry.level(), "");
+        assert_eq!(entry.message(), "plain text");
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        for line in [
+            "",
+            "stderr: No space left on device",
+            "2024-01-15 info: missing time",
+            "2024-13-15 10:23:45 +0000 UTC info: invalid month",
+            "2024-01-15 10:23:45 UTC info: missing offset",
+        ] {
+            assert!(line.parse::<Entry>().is_err(), "{line:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn joins_continuation_lines() {
+        let log = Log::from(LOG.to_owned());
+        let entries = log.entries();
+
+        assert_eq!(log.text(), LOG);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].level(), "error");
+        assert_eq!(
+            entries[1].message(),
+            "Artifact install failed: Payload: can not install Payload: rootfs.ext4: exit status 1\nstderr: No space left on device"
+        );
+        assert_eq!(entries[2].level(), "debug");
+    }
+
+    #[test]
+    fn drops_leading_continuation_lines_from_entries() {
+        let log = Log::from(
+            "garbage before the first entry\n2024-01-15 10:23:45 +0000 UTC info: started"
+                .to_owned(),
+        );
+
+        assert_eq!(log.entries().len(), 1);
+        assert_eq!(log.entries()[0].message(), "started");
+    }
+}
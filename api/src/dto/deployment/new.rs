@@ -1,6 +1,10 @@
+use chrono::{DateTime, Utc};
 use serde::Serialize;
+use serde_json::{Map, Value};
 use uuid::Uuid;
 
+use crate::dto::deployment::Phase;
+
 /// A new deployment request.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
 pub struct Deployment<'name, 'artifact, 'devices> {
@@ -11,8 +15,18 @@ pub struct Deployment<'name, 'artifact, 'devices> {
     #[serde(skip_serializing_if = "Option::is_none")]
     all_devices: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    filter_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    phases: Vec<Phase>,
+    #[serde(skip)]
+    start_time: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_devices: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     retries: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    update_control_map: Option<Map<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     autogenerate_delta: Option<bool>,
 }
 
@@ -25,7 +39,12 @@ impl<'name, 'artifact, 'devices> Deployment<'name, 'artifact, 'devices> {
             artifact_name,
             devices: None,
             all_devices: None,
+            filter_id: None,
+            phases: Vec::new(),
+            start_time: None,
+            max_devices: None,
             retries: None,
+            update_control_map: None,
             autogenerate_delta: None,
         }
     }
@@ -35,6 +54,7 @@ impl<'name, 'artifact, 'devices> Deployment<'name, 'artifact, 'devices> {
     pub const fn with_devices(mut self, devices: &'devices [Uuid]) -> Self {
         self.devices.replace(devices);
         self.all_devices = None;
+        self.filter_id = None;
         self
     }
 
@@ -43,6 +63,45 @@ impl<'name, 'artifact, 'devices> Deployment<'name, 'artifact, 'devices> {
     pub const fn for_all_devices(mut self) -> Self {
         self.devices = None;
         self.all_devices.replace(true);
+        self.filter_id = None;
+        self
+    }
+
+    /// Set the deployment for all devices matching a saved inventory filter.
+    #[must_use]
+    pub const fn with_filter_id(mut self, filter_id: Uuid) -> Self {
+        self.devices = None;
+        self.all_devices = None;
+        self.filter_id.replace(filter_id);
+        self
+    }
+
+    /// Set the phases of a phased deployment.
+    ///
+    /// If a start time has been set and the first phase has none, the first phase starts at
+    /// that time.
+    #[must_use]
+    pub fn with_phases(mut self, phases: Vec<Phase>) -> Self {
+        self.phases = phases;
+        self.apply_start_time();
+        self
+    }
+
+    /// Set the time at which the deployment starts.
+    ///
+    /// The server schedules deployments via their phases, so this sets the start time of the
+    /// first phase, unless it already has one.
+    #[must_use]
+    pub fn with_start_time(mut self, start_time: DateTime<Utc>) -> Self {
+        self.start_time.replace(start_time);
+        self.apply_start_time();
+        self
+    }
+
+    /// Set the maximum number of devices for the deployment.
+    #[must_use]
+    pub const fn with_max_devices(mut self, max_devices: usize) -> Self {
+        self.max_devices.replace(max_devices);
         self
     }
 
@@ -53,10 +112,32 @@ impl<'name, 'artifact, 'devices> Deployment<'name, 'artifact, 'devices> {
         self
     }
 
+    /// Set the update control map for the deployment.
+    #[must_use]
+    pub fn with_update_control_map(mut self, update_control_map: Map<String, Value>) -> Self {
+        self.update_control_map.replace(update_control_map);
+        self
+    }
+
     /// Set whether to autogenerate delta updates for the deployment.
     #[must_use]
     pub const fn with_autogenerate_delta(mut self, autogenerate: bool) -> Self {
         self.autogenerate_delta.replace(autogenerate);
         self
     }
+
+    /// Apply the start time, if any, to the first phase.
+    fn apply_start_time(&mut self) {
+        let Some(start_time) = self.start_time else {
+            return;
+        };
+
+        match self.phases.first_mut() {
+            Some(phase) if phase.start_time().is_none() => {
+                *phase = phase.with_start_time(start_time);
+            }
+            Some(_) => (),
+            None => self.phases.push(Phase::new().with_start_time(start_time)),
+        }
+    }
 }
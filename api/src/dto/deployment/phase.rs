@@ -0,0 +1,111 @@
+use std::fmt::Display;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// A phase of a phased deployment.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize)]
+pub struct Phase {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    batch_size: Option<u8>,
+    #[serde(rename = "start_ts", skip_serializing_if = "Option::is_none")]
+    start_time: Option<DateTime<Utc>>,
+}
+
+impl Phase {
+    /// Creates a new phase that covers the remaining devices and starts immediately.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            batch_size: None,
+            start_time: None,
+        }
+    }
+
+    /// Set the percentage of devices to include in this phase.
+    #[must_use]
+    pub const fn with_batch_size(mut self, percent: u8) -> Self {
+        self.batch_size.replace(percent);
+        self
+    }
+
+    /// Set the time at which this phase starts.
+    #[must_use]
+    pub const fn with_start_time(mut self, start_time: DateTime<Utc>) -> Self {
+        self.start_time.replace(start_time);
+        self
+    }
+
+    /// Returns the percentage of devices included in this phase, if set.
+    #[must_use]
+    pub const fn batch_size(&self) -> Option<u8> {
+        self.batch_size
+    }
+
+    /// Returns the time at which this phase starts, if set.
+    #[must_use]
+    pub const fn start_time(&self) -> Option<DateTime<Utc>> {
+        self.start_time
+    }
+}
+
+impl FromStr for Phase {
+    type Err = ParsePhaseError;
+
+    /// Parse a phase of the form `[PERCENT][@START_TIME]`, with the start time in RFC 3339 format.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (batch_size, start_time) = s.split_once('@').unwrap_or((s, ""));
+        let mut phase = Self::new();
+
+        if !batch_size.is_empty() {
+            phase = phase.with_batch_size(batch_size.parse()?);
+        }
+
+        if !start_time.is_empty() {
+            phase = phase.with_start_time(DateTime::parse_from_rfc3339(start_time)?.to_utc());
+        }
+
+        Ok(phase)
+    }
+}
+
+/// Error when parsing a [`Phase`] from a string.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParsePhaseError {
+    /// The batch size is not a valid percentage.
+    BatchSize(ParseIntError),
+    /// The start time is not a valid RFC 3339 timestamp.
+    StartTime(chrono::ParseError),
+}
+
+impl Display for ParsePhaseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BatchSize(error) => write!(f, "Invalid batch size: {error}"),
+            Self::StartTime(error) => write!(f, "Invalid start time: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ParsePhaseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::BatchSize(error) => Some(error),
+            Self::StartTime(error) => Some(error),
+        }
+    }
+}
+
+impl From<ParseIntError> for ParsePhaseError {
+    fn from(error: ParseIntError) -> Self {
+        Self::BatchSize(error)
+    }
+}
+
+impl From<chrono::ParseError> for ParsePhaseError {
+    fn from(error: chrono::ParseError) -> Self {
+        Self::StartTime(error)
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dto::deployment::DeviceStatus;
+
+/// Number of devices per deployment status.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Statistics {
+    #[serde(default)]
+    pending: usize,
+    #[serde(default)]
+    downloading: usize,
+    #[serde(default)]
+    pause_before_installing: usize,
+    #[serde(default)]
+    installing: usize,
+    #[serde(default)]
+    pause_before_rebooting: usize,
+    #[serde(default)]
+    rebooting: usize,
+    #[serde(default)]
+    pause_before_committing: usize,
+    #[serde(default)]
+    success: usize,
+    #[serde(default)]
+    failure: usize,
+    #[serde(default)]
+    noartifact: usize,
+    #[serde(default, rename = "already-installed")]
+    already_installed: usize,
+    #[serde(default)]
+    aborted: usize,
+    #[serde(default)]
+    decommissioned: usize,
+}
+
+impl Statistics {
+    /// Returns the number of devices with the given status.
+    #[must_use]
+    pub const fn get(&self, status: DeviceStatus) -> usize {
+        match status {
+            DeviceStatus::Pending => self.pending,
+            DeviceStatus::Downloading => self.downloading,
+            DeviceStatus::PauseBeforeInstalling => self.pause_before_installing,
+            DeviceStatus::Installing => self.installing,
+            DeviceStatus::PauseBeforeRebooting => self.pause_before_rebooting,
+            DeviceStatus::Rebooting => self.rebooting,
+            DeviceStatus::PauseBeforeCommitting => self.pause_before_committing,
+            DeviceStatus::Success => self.success,
+            DeviceStatus::Failure => self.failure,
+            DeviceStatus::NoArtifact => self.noartifact,
+            DeviceStatus::AlreadyInstalled => self.already_installed,
+            DeviceStatus::Aborted => self.aborted,
+            DeviceStatus::Decommissioned => self.decommissioned,
+        }
+    }
+
+    /// Iterate over all statuses and their respective number of devices.
+    pub fn iter(&self) -> impl Iterator<Item = (DeviceStatus, usize)> {
+        [
+            DeviceStatus::Pending,
+            DeviceStatus::Downloading,
+            DeviceStatus::PauseBeforeInstalling,
+            DeviceStatus::Installing,
+            DeviceStatus::PauseBeforeRebooting,
+            DeviceStatus::Rebooting,
+            DeviceStatus::PauseBeforeCommitting,
+            DeviceStatus::Success,
+            DeviceStatus::Failure,
+            DeviceStatus::NoArtifact,
+            DeviceStatus::AlreadyInstalled,
+            DeviceStatus::Aborted,
+            DeviceStatus::Decommissioned,
+        ]
+        .into_iter()
+        .map(|status| (status, self.get(status)))
+    }
+
+    /// Returns the total number of devices.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.iter().map(|(_, count)| count).sum()
+    }
+}
+
+impl Display for Statistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            for (status, count) in self.iter() {
+                writeln!(f, "{status}: {count}")?;
+            }
+
+            write!(f, "total: {}", self.total())
+        } else {
+            write!(f, "Statistics {{ ")?;
+
+            for (status, count) in self.iter().filter(|(_, count)| *count > 0) {
+                write!(f, "{status}: {count}, ")?;
+            }
+
+            write!(f, "total: {} }}", self.total())
+        }
+    }
+}
+
+/// Statistics response, which newer servers wrap along with the total artifact size.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Response {
+    Wrapped { status: Statistics },
+    Plain(Statistics),
+}
+
+impl From<Response> for Statistics {
+    fn from(response: Response) -> Self {
+        match response {
+            Response::Wrapped { status } | Response::Plain(status) => status,
+        }
+    }
+}
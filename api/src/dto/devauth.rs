@@ -0,0 +1,5 @@
+//! Device authentication data structures and types.
+
+pub mod device;
+pub mod preauthorization;
+pub mod put;
@@ -0,0 +1,114 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dto::{AuthentificationSet, Identity, Status};
+
+/// A device as seen by the device authentication service.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Device {
+    id: Uuid,
+    #[serde(rename = "identity_data")]
+    identity: Identity,
+    status: Status,
+    decommissioning: bool,
+    created_ts: DateTime<FixedOffset>,
+    updated_ts: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    check_in_time: Option<DateTime<FixedOffset>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    auth_sets: Vec<AuthentificationSet>,
+}
+
+impl Device {
+    /// Returns the ID of the device.
+    #[must_use]
+    pub const fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Returns the identity of the device.
+    #[must_use]
+    pub const fn identity(&self) -> Identity {
+        self.identity
+    }
+
+    /// Returns the authentication status of the device.
+    #[must_use]
+    pub const fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Returns whether the device is being decommissioned.
+    #[must_use]
+    pub const fn decommissioning(&self) -> bool {
+        self.decommissioning
+    }
+
+    /// Returns the creation timestamp of the device.
+    #[must_use]
+    pub const fn created_ts(&self) -> DateTime<FixedOffset> {
+        self.created_ts
+    }
+
+    /// Returns the updated timestamp of the device.
+    #[must_use]
+    pub const fn updated_ts(&self) -> DateTime<FixedOffset> {
+        self.updated_ts
+    }
+
+    /// Returns the time of the device's last check-in, if any.
+    #[must_use]
+    pub const fn check_in_time(&self) -> Option<DateTime<FixedOffset>> {
+        self.check_in_time
+    }
+
+    /// Returns the authentication sets of the device.
+    #[must_use]
+    pub fn auth_sets(&self) -> &[AuthentificationSet] {
+        &self.auth_sets
+    }
+
+    /// Iterate over the authentication sets with the given status.
+    pub fn auth_sets_with_status(
+        &self,
+        status: Status,
+    ) -> impl Iterator<Item = &AuthentificationSet> {
+        self.auth_sets
+            .iter()
+            .filter(move |auth_set| auth_set.status() == status)
+    }
+}
+
+impl Display for Device {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            writeln!(
+                f,
+                "Device: {} ({})\n\t- status: {}\n\t- created: {}\n\t- updated: {}\n\t- auth sets:",
+                self.id,
+                self.identity.mac(),
+                self.status,
+                self.created_ts,
+                self.updated_ts
+            )?;
+
+            for auth_set in &self.auth_sets {
+                writeln!(
+                    f,
+                    "\t\t- {} [{}] {}",
+                    auth_set.id(),
+                    auth_set.status(),
+                    auth_set.timestamp()
+                )?;
+            }
+
+            Ok(())
+        } else {
+            write!(f, "{} ({}) [{}]", self.id, self.identity.mac(), self.status)
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Payload for preauthorizing devices.
+
+use serde::Serialize;
+
+use crate::dto::Identity;
+
+/// A request to preauthorize a device identity with its public key.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct Preauthorization<'pubkey> {
+    #[serde(rename = "identity_data")]
+    identity: Identity,
+    pubkey: &'pubkey str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    force: Option<bool>,
+}
+
+impl<'pubkey> Preauthorization<'pubkey> {
+    /// Creates a new `Preauthorization` instance.
+    #[must_use]
+    pub const fn new(identity: Identity, pubkey: &'pubkey str) -> Self {
+        Self {
+            identity,
+            pubkey,
+            force: None,
+        }
+    }
+
+    /// Set whether to overwrite an existing, not yet accepted device with the same identity.
+    #[must_use]
+    pub const fn with_force(mut self, force: bool) -> Self {
+        self.force.replace(force);
+        self
+    }
+}
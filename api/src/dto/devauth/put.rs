@@ -0,0 +1,19 @@
+//! Payload for authentication set PUT requests.
+
+use serde::Serialize;
+
+use crate::dto::Status;
+
+/// An authentication set status update request.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct AuthSet {
+    status: Status,
+}
+
+impl AuthSet {
+    /// Creates a new `AuthSet` instance.
+    #[must_use]
+    pub const fn new(status: Status) -> Self {
+        Self { status }
+    }
+}
@@ -10,10 +10,29 @@ pub enum DeviceType {
     Paulmann,
 }
 
-impl Display for DeviceType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl DeviceType {
+    /// Return a static string representation of the device type.
+    #[must_use]
+    pub const fn to_str(self) -> &'static str {
         match self {
-            Self::Paulmann => write!(f, "paulmann"),
+            Self::Paulmann => "paulmann",
         }
     }
 }
+
+impl Display for DeviceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+#[cfg(feature = "clap")]
+impl clap::ValueEnum for DeviceType {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Paulmann]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(clap::builder::PossibleValue::new(self.to_str()))
+    }
+}
@@ -0,0 +1,65 @@
+//! Saved inventory filters.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dto::{Predicate, Search};
+
+pub mod new;
+
+/// A saved inventory filter, defining a dynamic group of devices.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Filter {
+    id: Uuid,
+    name: String,
+    terms: Vec<Predicate>,
+}
+
+impl Filter {
+    /// Returns the ID of the filter.
+    #[must_use]
+    pub const fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Returns the name of the filter.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the predicates that devices must match.
+    #[must_use]
+    pub fn terms(&self) -> &[Predicate] {
+        &self.terms
+    }
+}
+
+impl From<&Filter> for Search {
+    fn from(filter: &Filter) -> Self {
+        filter.terms.iter().cloned().collect()
+    }
+}
+
+impl Display for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            writeln!(
+                f,
+                "Filter: {}\n\t- name: {}\n\t- terms:",
+                self.id, self.name
+            )?;
+
+            for term in &self.terms {
+                writeln!(f, "\t\t- {term}")?;
+            }
+
+            Ok(())
+        } else {
+            write!(f, "{} ({})", self.id, self.name)
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+use crate::dto::Predicate;
+
+/// A new saved inventory filter.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Filter<'name, 'terms> {
+    name: &'name str,
+    terms: &'terms [Predicate],
+}
+
+impl<'name, 'terms> Filter<'name, 'terms> {
+    /// Creates a new `Filter` instance.
+    #[must_use]
+    pub const fn new(name: &'name str, terms: &'terms [Predicate]) -> Self {
+        Self { name, terms }
+    }
+}
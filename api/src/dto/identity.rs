@@ -12,6 +12,12 @@ pub struct Identity {
 }
 
 impl Identity {
+    /// Create a new identity from the device's MAC address.
+    #[must_use]
+    pub const fn new(mac: MacAddr6) -> Self {
+        Self { mac }
+    }
+
     /// Return the MAC address of the device.
     #[must_use]
     pub const fn mac(&self) -> MacAddr6 {
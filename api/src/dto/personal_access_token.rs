@@ -0,0 +1,77 @@
+//! Personal access tokens of a user.
+
+use std::fmt::Display;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub mod new;
+
+/// A personal access token, as listed by the server.
+///
+/// The token itself is only ever returned once, when it is created.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
+pub struct PersonalAccessToken {
+    id: Uuid,
+    name: String,
+    expiration_date: DateTime<FixedOffset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_used: Option<DateTime<FixedOffset>>,
+    created_ts: DateTime<FixedOffset>,
+}
+
+impl PersonalAccessToken {
+    /// Returns the ID of the token.
+    #[must_use]
+    pub const fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Returns the name of the token.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the time at which the token expires.
+    #[must_use]
+    pub const fn expiration_date(&self) -> DateTime<FixedOffset> {
+        self.expiration_date
+    }
+
+    /// Returns the time at which the token was last used, if ever.
+    #[must_use]
+    pub const fn last_used(&self) -> Option<DateTime<FixedOffset>> {
+        self.last_used
+    }
+
+    /// Returns the time at which the token was created.
+    #[must_use]
+    pub const fn created_ts(&self) -> DateTime<FixedOffset> {
+        self.created_ts
+    }
+}
+
+impl Display for PersonalAccessToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            writeln!(f, "Personal access token: {}", self.id)?;
+            writeln!(f, "\t- name: {}", self.name)?;
+            writeln!(f, "\t- created: {}", self.created_ts)?;
+            writeln!(f, "\t- expires: {}", self.expiration_date)?;
+
+            if let Some(last_used) = self.last_used {
+                writeln!(f, "\t- last used: {last_used}")?;
+            }
+
+            Ok(())
+        } else {
+            write!(
+                f,
+                "{} ({}, expires {})",
+                self.id, self.name, self.expiration_date
+            )
+        }
+    }
+}
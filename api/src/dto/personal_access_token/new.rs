@@ -0,0 +1,16 @@
+use serde::Serialize;
+
+/// A request to create a new personal access token.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct PersonalAccessToken<'name> {
+    name: &'name str,
+    expires_in: u64,
+}
+
+impl<'name> PersonalAccessToken<'name> {
+    /// Creates a new `PersonalAccessToken` instance, expiring after the given number of seconds.
+    #[must_use]
+    pub const fn new(name: &'name str, expires_in: u64) -> Self {
+        Self { name, expires_in }
+    }
+}
@@ -0,0 +1,95 @@
+//! Inventory search queries.
+
+use serde::Serialize;
+use uuid::Uuid;
+
+pub use self::predicate::{Operator, ParsePredicateError, Predicate};
+pub use self::selection::Selection;
+pub use self::sort::{Order, Sort};
+use crate::dto::Scope;
+
+mod predicate;
+mod selection;
+mod sort;
+
+/// A device search query of the inventory.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct Search {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    filters: Vec<Predicate>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sort: Vec<Sort>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attributes: Vec<Selection>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    device_ids: Vec<Uuid>,
+}
+
+impl Search {
+    /// Creates a new search query that matches all devices.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            filters: Vec::new(),
+            sort: Vec::new(),
+            attributes: Vec::new(),
+            device_ids: Vec::new(),
+        }
+    }
+
+    /// Add a predicate that devices must match.
+    #[must_use]
+    pub fn with_filter(mut self, predicate: Predicate) -> Self {
+        self.filters.push(predicate);
+        self
+    }
+
+    /// Sort results by the given attribute.
+    ///
+    /// Subsequent sort criteria are applied to results that compare equal on the previous ones.
+    #[must_use]
+    pub fn with_sort<A>(mut self, scope: Scope, attribute: A, order: Order) -> Self
+    where
+        A: Into<String>,
+    {
+        self.sort.push(Sort::new(scope, attribute, order));
+        self
+    }
+
+    /// Only return the given attribute of the devices.
+    ///
+    /// If no attributes are selected, all attributes are returned.
+    #[must_use]
+    pub fn with_attribute<A>(mut self, scope: Scope, attribute: A) -> Self
+    where
+        A: Into<String>,
+    {
+        self.attributes.push(Selection::new(scope, attribute));
+        self
+    }
+
+    /// Restrict the search to the given devices.
+    #[must_use]
+    pub fn with_device_ids(mut self, device_ids: Vec<Uuid>) -> Self {
+        self.device_ids = device_ids;
+        self
+    }
+
+    /// Returns the predicates of the search.
+    #[must_use]
+    pub fn filters(&self) -> &[Predicate] {
+        &self.filters
+    }
+}
+
+impl FromIterator<Predicate> for Search {
+    fn from_iter<T>(predicates: T) -> Self
+    where
+        T: IntoIterator<Item = Predicate>,
+    {
+        Self {
+            filters: predicates.into_iter().collect(),
+            ..Self::new()
+        }
+    }
+}
@@ -0,0 +1,462 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use macaddr::MacAddr6;
+use serde::de::IntoDeserializer;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::dto::Scope;
+
+/// A filter predicate on a device attribute.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Predicate {
+    scope: Scope,
+    attribute: String,
+    #[serde(flatten)]
+    operator: Operator,
+}
+
+/// Comparison operators of filter predicates along with their operands.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", content = "value")]
+pub enum Operator {
+    /// The attribute equals the value.
+    #[serde(rename = "$eq")]
+    Eq(Value),
+    /// The attribute does not equal the value.
+    #[serde(rename = "$ne")]
+    Ne(Value),
+    /// The attribute is greater than the value.
+    #[serde(rename = "$gt")]
+    Gt(Value),
+    /// The attribute is greater than or equal to the value.
+    #[serde(rename = "$gte")]
+    Gte(Value),
+    /// The attribute is less than the value.
+    #[serde(rename = "$lt")]
+    Lt(Value),
+    /// The attribute is less than or equal to the value.
+    #[serde(rename = "$lte")]
+    Lte(Value),
+    /// The attribute equals any of the values.
+    #[serde(rename = "$in")]
+    In(Vec<Value>),
+    /// The attribute equals none of the values.
+    #[serde(rename = "$nin")]
+    Nin(Vec<Value>),
+    /// The attribute exists or does not exist.
+    #[serde(rename = "$exists")]
+    Exists(bool),
+    /// The attribute matches the regular expression.
+    #[serde(rename = "$regex")]
+    Regex(String),
+}
+
+impl Predicate {
+    /// Creates a new predicate on the given attribute.
+    #[must_use]
+    pub fn new<A>(scope: Scope, attribute: A, operator: Operator) -> Self
+    where
+        A: Into<String>,
+    {
+        Self {
+            scope,
+            attribute: attribute.into(),
+            operator,
+        }
+    }
+
+    /// The attribute equals the value.
+    #[must_use]
+    pub fn eq<A, V>(scope: Scope, attribute: A, value: V) -> Self
+    where
+        A: Into<String>,
+        V: Into<Value>,
+    {
+        Self::new(scope, attribute, Operator::Eq(value.into()))
+    }
+
+    /// The attribute does not equal the value.
+    #[must_use]
+    pub fn ne<A, V>(scope: Scope, attribute: A, value: V) -> Self
+    where
+        A: Into<String>,
+        V: Into<Value>,
+    {
+        Self::new(scope, attribute, Operator::Ne(value.into()))
+    }
+
+    /// The attribute is greater than the value.
+    #[must_use]
+    pub fn gt<A, V>(scope: Scope, attribute: A, value: V) -> Self
+    where
+        A: Into<String>,
+        V: Into<Value>,
+    {
+        Self::new(scope, attribute, Operator::Gt(value.into()))
+    }
+
+    /// The attribute is greater than or equal to the value.
+    #[must_use]
+    pub fn gte<A, V>(scope: Scope, attribute: A, value: V) -> Self
+    where
+        A: Into<String>,
+        V: Into<Value>,
+    {
+        Self::new(scope, attribute, Operator::Gte(value.into()))
+    }
+
+    /// The attribute is less than the value.
+    #[must_use]
+    pub fn lt<A, V>(scope: Scope, attribute: A, value: V) -> Self
+    where
+        A: Into<String>,
+        V: Into<Value>,
+    {
+        Self::new(scope, attribute, Operator::Lt(value.into()))
+    }
+
+    /// The attribute is less than or equal to the value.
+    #[must_use]
+    pub fn lte<A, V>(scope: Scope, attribute: A, value: V) -> Self
+    where
+        A: Into<String>,
+        V: Into<Value>,
+    {
+        Self::new(scope, attribute, Operator::Lte(value.into()))
+    }
+
+    /// The attribute equals any of the values.
+    #[must_use]
+    pub fn any_of<A, V>(scope: Scope, attribute: A, values: V) -> Self
+    where
+        A: Into<String>,
+        V: IntoIterator<Item: Into<Value>>,
+    {
+        Self::new(
+            scope,
+            attribute,
+            Operator::In(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// The attribute equals none of the values.
+    #[must_use]
+    pub fn none_of<A, V>(scope: Scope, attribute: A, values: V) -> Self
+    where
+        A: Into<String>,
+        V: IntoIterator<Item: Into<Value>>,
+    {
+        Self::new(
+            scope,
+            attribute,
+            Operator::Nin(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// The attribute exists or not.
+    #[must_use]
+    pub fn exists<A>(scope: Scope, attribute: A, exists: bool) -> Self
+    where
+        A: Into<String>,
+    {
+        Self::new(scope, attribute, Operator::Exists(exists))
+    }
+
+    /// The attribute matches the regular expression.
+    #[must_use]
+    pub fn regex<A, R>(scope: Scope, attribute: A, regex: R) -> Self
+    where
+        A: Into<String>,
+        R: Into<String>,
+    {
+        Self::new(scope, attribute, Operator::Regex(regex.into()))
+    }
+
+    /// The device has the given MAC address.
+    #[must_use]
+    pub fn mac_address(mac_address: MacAddr6) -> Self {
+        Self::eq(
+            Scope::Identity,
+            "mac",
+            mac_address.to_string().to_lowercase(),
+        )
+    }
+
+    /// The device has the given host name.
+    #[must_use]
+    pub fn hostname<H>(hostname: H) -> Self
+    where
+        H: Into<String>,
+    {
+        Self::eq(Scope::Inventory, "hostname", hostname.into())
+    }
+
+    /// The device has the artifact with the given name installed.
+    #[must_use]
+    pub fn artifact_name<N>(artifact_name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self::eq(Scope::Inventory, "artifact_name", artifact_name.into())
+    }
+
+    /// The device has the given root filesystem image version installed.
+    #[must_use]
+    pub fn rootfs_image_version<V>(version: V) -> Self
+    where
+        V: Into<String>,
+    {
+        Self::eq(Scope::Inventory, "rootfs-image.version", version.into())
+    }
+
+    /// The device is of the given device type.
+    #[must_use]
+    pub fn device_type<T>(device_type: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::eq(Scope::Inventory, "device_type", device_type.into())
+    }
+
+    /// Returns the scope of the attribute.
+    #[must_use]
+    pub const fn scope(&self) -> Scope {
+        self.scope
+    }
+
+    /// Returns the name of the attribute.
+    #[must_use]
+    pub fn attribute(&self) -> &str {
+        &self.attribute
+    }
+
+    /// Returns the operator along with its operand.
+    #[must_use]
+    pub const fn operator(&self) -> &Operator {
+        &self.operator
+    }
+}
+
+impl Display for Predicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} {}", self.scope, self.attribute, self.operator)
+    }
+}
+
+impl FromStr for Predicate {
+    type Err = ParsePredicateError;
+
+    /// Parse a predicate of the form `SCOPE/ATTRIBUTE $OPERATOR VALUE`, e.g.
+    /// `inventory/device_type $in ["foo", "bar"]`.
+    ///
+    /// The value is parsed as JSON, falling back to a plain string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.trim().splitn(3, char::is_whitespace);
+        let (scope, attribute) = fields
+            .next()
+            .and_then(|field| field.split_once('/'))
+            .ok_or(ParsePredicateError::MissingAttribute)?;
+        let scope = Scope::deserialize(scope.into_deserializer()).map_err(
+            |_: serde::de::value::Error| ParsePredicateError::InvalidScope(scope.into()),
+        )?;
+        let operator = fields.next().ok_or(ParsePredicateError::MissingOperator)?;
+        let value = fields.next().unwrap_or_default().trim();
+        let value = serde_json::from_str(value).unwrap_or_else(|_| Value::from(value));
+        let operator = serde_json::from_value(json!({ "type": operator, "value": value }))
+            .map_err(ParsePredicateError::InvalidOperator)?;
+        Ok(Self::new(scope, attribute, operator))
+    }
+}
+
+/// Error when parsing a [`Predicate`] from a string.
+#[derive(Debug)]
+pub enum ParsePredicateError {
+    /// The `SCOPE/ATTRIBUTE` part is missing.
+    MissingAttribute,
+    /// The scope is unknown.
+    InvalidScope(String),
+    /// The operator is missing.
+    MissingOperator,
+    /// The operator is unknown or its value is invalid.
+    InvalidOperator(serde_json::Error),
+}
+
+impl Display for ParsePredicateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingAttribute => write!(f, "Missing SCOPE/ATTRIBUTE"),
+            Self::InvalidScope(scope) => write!(f, "Invalid scope: {scope}"),
+            Self::MissingOperator => write!(f, "Missing operator"),
+            Self::InvalidOperator(error) => write!(f, "Invalid operator: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ParsePredicateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidOperator(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl Operator {
+    /// Return the operator's name as used by the API.
+    #[must_use]
+    pub const fn to_str(&self) -> &'static str {
+        match self {
+            Self::Eq(_) => "$eq",
+            Self::Ne(_) => "$ne",
+            Self::Gt(_) => "$gt",
+            Self::Gte(_) => "$gte",
+            Self::Lt(_) => "$lt",
+            Self::Lte(_) => "$lte",
+            Self::In(_) => "$in",
+            Self::Nin(_) => "$nin",
+            Self::Exists(_) => "$exists",
+            Self::Regex(_) => "$regex",
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Eq(value)
+            | Self::Ne(value)
+            | Self::Gt(value)
+            | Self::Gte(value)
+            | Self::Lt(value)
+            | Self::Lte(value) => write!(f, "{} {value}", self.to_str()),
+            Self::In(values) | Self::Nin(values) => {
+                write!(f, "{} {}", self.to_str(), Value::from(values.clone()))
+            }
+            Self::Exists(exists) => write!(f, "{} {exists}", self.to_str()),
+            Self::Regex(regex) => write!(f, "{} {regex:?}", self.to_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Operator, ParsePredicateError, Predicate};
+    use crate::dto::Scope;
+
+    #[test]
+    fn serializes_to_search_filter() {
+        let predicate = Predicate::any_of(Scope::Inventory, "device_type", ["foo", "bar"]);
+        assert_eq!(
+            serde_json::to_value(&predicate).unwrap(),
+            json!({
+                "scope": "inventory",
+                "attribute": "device_type",
+                "type": "$in",
+                "value": ["foo", "bar"],
+            })
+        );
+    }
+
+    #[test]
+    fn deserializes_search_filter() {
+        let predicate: Predicate = serde_json::from_value(json!({
+            "scope": "identity",
+            "attribute": "mac",
+            "type": "$eq",
+            "value": "00:11:22:33:44:55",
+        }))
+        .unwrap();
+        assert_eq!(
+            predicate,
+            Predicate::eq(Scope::Identity, "mac", "00:11:22:33:44:55")
+        );
+    }
+
+    #[test]
+    fn round_trips_all_operators() {
+        for predicate in [
+            Predicate::eq(Scope::Inventory, "hostname", "foo"),
+            Predicate::ne(Scope::Tags, "location", "lab"),
+            Predicate::gt(Scope::System, "updated_ts", 1),
+            Predicate::gte(Scope::System, "updated_ts", 2),
+            Predicate::lt(Scope::System, "updated_ts", 3),
+            Predicate::lte(Scope::System, "updated_ts", 4),
+            Predicate::any_of(Scope::Inventory, "device_type", ["foo"]),
+            Predicate::none_of(Scope::Inventory, "device_type", ["bar"]),
+            Predicate::exists(Scope::Inventory, "ipv4_wlan0", false),
+            Predicate::regex(Scope::Inventory, "artifact_name", "^release-"),
+        ] {
+            let json = serde_json::to_string(&predicate).unwrap();
+            assert_eq!(serde_json::from_str::<Predicate>(&json).unwrap(), predicate);
+            assert_eq!(
+                predicate.to_string().parse::<Predicate>().unwrap(),
+                predicate
+            );
+        }
+    }
+
+    #[test]
+    fn parses_json_values() {
+        let predicate: Predicate = r#"inventory/device_type $in ["foo", "bar"]"#.parse().unwrap();
+        assert_eq!(
+            predicate,
+            Predicate::any_of(Scope::Inventory, "device_type", ["foo", "bar"])
+        );
+        let predicate: Predicate = "system/updated_ts $gt 42".parse().unwrap();
+        assert_eq!(predicate.operator(), &Operator::Gt(json!(42)));
+    }
+
+    #[test]
+    fn parses_plain_string_values() {
+        let predicate: Predicate = "  inventory/hostname $eq my host  ".parse().unwrap();
+        assert_eq!(predicate, Predicate::hostname("my host"));
+    }
+
+    #[test]
+    fn rejects_missing_attribute() {
+        assert!(matches!(
+            "device_type $eq foo".parse::<Predicate>(),
+            Err(ParsePredicateError::MissingAttribute)
+        ));
+        assert!(matches!(
+            "".parse::<Predicate>(),
+            Err(ParsePredicateError::MissingAttribute)
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_scope() {
+        assert!(matches!(
+            "device/device_type $eq foo".parse::<Predicate>(),
+            Err(ParsePredicateError::InvalidScope(scope)) if scope == "device"
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_operator() {
+        assert!(matches!(
+            "inventory/device_type".parse::<Predicate>(),
+            Err(ParsePredicateError::MissingOperator)
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_operator() {
+        assert!(matches!(
+            "inventory/device_type $like foo".parse::<Predicate>(),
+            Err(ParsePredicateError::InvalidOperator(_))
+        ));
+        assert!(matches!(
+            "inventory/device_type $in foo".parse::<Predicate>(),
+            Err(ParsePredicateError::InvalidOperator(_))
+        ));
+        assert!(matches!(
+            "inventory/ipv4_wlan0 $exists maybe".parse::<Predicate>(),
+            Err(ParsePredicateError::InvalidOperator(_))
+        ));
+    }
+}
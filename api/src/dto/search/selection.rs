@@ -0,0 +1,24 @@
+use serde::Serialize;
+
+use crate::dto::Scope;
+
+/// An attribute to include in search results.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct Selection {
+    scope: Scope,
+    attribute: String,
+}
+
+impl Selection {
+    /// Creates a new attribute selection.
+    #[must_use]
+    pub fn new<A>(scope: Scope, attribute: A) -> Self
+    where
+        A: Into<String>,
+    {
+        Self {
+            scope,
+            attribute: attribute.into(),
+        }
+    }
+}
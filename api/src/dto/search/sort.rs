@@ -0,0 +1,37 @@
+use serde::Serialize;
+
+use crate::dto::Scope;
+
+/// Sort order of search results.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+    /// Ascending order.
+    #[default]
+    Asc,
+    /// Descending order.
+    Desc,
+}
+
+/// A sort criterion of search results.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct Sort {
+    scope: Scope,
+    attribute: String,
+    order: Order,
+}
+
+impl Sort {
+    /// Creates a new sort criterion.
+    #[must_use]
+    pub fn new<A>(scope: Scope, attribute: A, order: Order) -> Self
+    where
+        A: Into<String>,
+    {
+        Self {
+            scope,
+            attribute: attribute.into(),
+            order,
+        }
+    }
+}
@@ -14,6 +14,8 @@ pub enum Status {
     NoAuth,
     /// Request was rejected.
     Rejected,
+    /// Device has been preauthorized.
+    Preauthorized,
 }
 
 impl Status {
@@ -25,6 +27,7 @@ impl Status {
             Self::Accepted => "accepted",
             Self::NoAuth => "noauth",
             Self::Rejected => "rejected",
+            Self::Preauthorized => "preauthorized",
         }
     }
 }
@@ -38,7 +41,13 @@ impl Display for Status {
 #[cfg(feature = "clap")]
 impl clap::ValueEnum for Status {
     fn value_variants<'a>() -> &'a [Self] {
-        &[Self::Pending, Self::Accepted, Self::NoAuth, Self::Rejected]
+        &[
+            Self::Pending,
+            Self::Accepted,
+            Self::NoAuth,
+            Self::Rejected,
+            Self::Preauthorized,
+        ]
     }
 
     fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
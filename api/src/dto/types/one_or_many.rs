@@ -3,7 +3,7 @@ use std::vec::IntoIter;
 
 use serde::{Deserialize, Serialize};
 
-use crate::utils::DisplaySlice;
+use crate::utils::{DisplaySlice, join};
 
 /// A wrapper type to represent either a single item or a list of items.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
@@ -25,6 +25,19 @@ impl<T> OneOrMany<T> {
     }
 }
 
+impl<T> OneOrMany<T>
+where
+    T: Display,
+{
+    /// Join the items' string representations with the given separator.
+    pub fn join(&self, separator: &str) -> String {
+        match self {
+            Self::One(item) => item.to_string(),
+            Self::Many(items) => join(items, separator),
+        }
+    }
+}
+
 impl<T> From<T> for OneOrMany<T> {
     fn from(item: T) -> Self {
         Self::One(item)
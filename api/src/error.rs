@@ -0,0 +1,236 @@
+//! Errors of the Mender server API.
+
+use std::fmt::Display;
+
+use reqwest::{Response, StatusCode};
+use serde::Deserialize;
+
+/// Header in which the Mender server sends the ID of a request.
+const REQUEST_ID_HEADER: &str = "X-MEN-RequestID";
+
+/// Result type of the Mender server API.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors of the Mender server API.
+#[derive(Debug)]
+pub enum Error {
+    /// The request was not authenticated, e.g. due to an invalid or expired token (HTTP 401).
+    Unauthorized(ApiError),
+    /// The request was authenticated, but is not allowed for the user (HTTP 403).
+    Forbidden(ApiError),
+    /// The login requires a second factor, which was missing or invalid.
+    SecondFactorRequired(ApiError),
+    /// The requested resource does not exist (HTTP 404).
+    NotFound(ApiError),
+    /// The request conflicts with the current state of a resource (HTTP 409).
+    Conflict(ApiError),
+    /// The request was rejected as invalid (HTTP 400 or 422).
+    Validation(ApiError),
+    /// Too many requests were made (HTTP 429).
+    RateLimited(ApiError),
+    /// The server responded with any other unsuccessful status.
+    Status(ApiError),
+    /// The HTTP client could not be built, e.g. due to an invalid certificate or proxy.
+    Client(reqwest::Error),
+    /// The request could not be sent or the response could not be received.
+    Transport(reqwest::Error),
+    /// The response body could not be decoded.
+    Decode {
+        /// The underlying decoding error.
+        error: serde_json::Error,
+        /// The ID of the request, if the server sent one.
+        request_id: Option<String>,
+    },
+}
+
+impl Error {
+    /// Create an error from an unsuccessful response, consuming its body.
+    pub(crate) async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let request_id = request_id(&response);
+
+        match response.bytes().await {
+            Ok(body) => ApiError::new(status, request_id, &body).into(),
+            Err(error) => Self::Transport(error),
+        }
+    }
+
+    /// Returns the HTTP status code of the response, if any.
+    #[must_use]
+    pub const fn status(&self) -> Option<StatusCode> {
+        match self.api_error() {
+            Some(error) => Some(error.status),
+            None => None,
+        }
+    }
+
+    /// Returns the ID of the request, if the server sent one.
+    #[must_use]
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Self::Decode { request_id, .. } => request_id.as_deref(),
+            other => other.api_error().and_then(ApiError::request_id),
+        }
+    }
+
+    /// Returns the error message sent by the server, if any.
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        self.api_error().and_then(ApiError::message)
+    }
+
+    /// Returns the error sent by the server, if any.
+    #[must_use]
+    pub const fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Self::Unauthorized(error)
+            | Self::Forbidden(error)
+            | Self::SecondFactorRequired(error)
+            | Self::NotFound(error)
+            | Self::Conflict(error)
+            | Self::Validation(error)
+            | Self::RateLimited(error)
+            | Self::Status(error) => Some(error),
+            Self::Client(_) | Self::Transport(_) | Self::Decode { .. } => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unauthorized(error) => write!(f, "Unauthorized: {error}"),
+            Self::Forbidden(error) => write!(f, "Forbidden: {error}"),
+            Self::SecondFactorRequired(error) => {
+                write!(f, "Two-factor authentication required: {error}")
+            }
+            Self::NotFound(error) => write!(f, "Not found: {error}"),
+            Self::Conflict(error) => write!(f, "Conflict: {error}"),
+            Self::Validation(error) => write!(f, "Invalid request: {error}"),
+            Self::RateLimited(error) => write!(f, "Rate limited: {error}"),
+            Self::Status(error) => write!(f, "Request failed: {error}"),
+            Self::Client(error) => write!(f, "Failed to build HTTP client: {error}"),
+            Self::Transport(error) => write!(f, "Transport error: {error}"),
+            Self::Decode { error, request_id } => {
+                write!(f, "Invalid response: {error}")?;
+
+                if let Some(request_id) = request_id {
+                    write!(f, " (request ID: {request_id})")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Client(error) | Self::Transport(error) => Some(error),
+            Self::Decode { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Self::Transport(error)
+    }
+}
+
+impl From<ApiError> for Error {
+    fn from(error: ApiError) -> Self {
+        match error.status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized(error),
+            StatusCode::FORBIDDEN => Self::Forbidden(error),
+            StatusCode::NOT_FOUND => Self::NotFound(error),
+            StatusCode::CONFLICT => Self::Conflict(error),
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => Self::Validation(error),
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited(error),
+            _ => Self::Status(error),
+        }
+    }
+}
+
+/// An error response of the Mender server.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ApiError {
+    status: StatusCode,
+    message: Option<String>,
+    request_id: Option<String>,
+}
+
+impl ApiError {
+    /// Parse an error response body of the form `{"error": ..., "request_id": ...}`.
+    ///
+    /// Bodies that are not of this form are used as the message verbatim.
+    pub(crate) fn new(status: StatusCode, request_id: Option<String>, body: &[u8]) -> Self {
+        let (message, body_request_id) = serde_json::from_slice::<ErrorBody>(body).map_or_else(
+            |_| {
+                let text = String::from_utf8_lossy(body).trim().to_string();
+                ((!text.is_empty()).then_some(text), None)
+            },
+            |body| (body.error, body.request_id),
+        );
+
+        Self {
+            status,
+            message,
+            request_id: body_request_id.or(request_id),
+        }
+    }
+
+    /// Returns the HTTP status code of the response.
+    #[must_use]
+    pub const fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns the error message sent by the server, if any.
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Returns the ID of the request, if the server sent one.
+    #[must_use]
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.status)?;
+
+        if let Some(message) = &self.message {
+            write!(f, ": {message}")?;
+        }
+
+        if let Some(request_id) = &self.request_id {
+            write!(f, " (request ID: {request_id})")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Error body as sent by the Mender server.
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    error: Option<String>,
+    request_id: Option<String>,
+}
+
+/// Extract the request ID from the response headers.
+pub fn request_id(response: &Response) -> Option<String> {
+    response
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string)
+}
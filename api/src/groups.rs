@@ -6,13 +6,14 @@ use crate::PaginatedIterator;
 use crate::dto::PatchGroupResponse;
 use crate::pagination::{DEFAULT_PAGE_SIZE, Pager};
 use crate::session::Session;
+use crate::utils::{RequestBuilderExt, ResponseExt};
 
 const PATH: &str = "/api/management/v1/inventory/groups";
 
 /// Manage groups on the Mender server.
 pub trait Groups {
     /// List all groups available in the Mender server.
-    fn list(&self) -> impl Future<Output = reqwest::Result<Vec<String>>> + Send;
+    fn list(&self) -> impl Future<Output = crate::Result<Vec<String>>> + Send;
 
     /// List all device that are members of the specified group.
     fn devices_of(
@@ -26,16 +27,17 @@ pub trait Groups {
         &self,
         name: &str,
         devices: &[Uuid],
-    ) -> impl Future<Output = reqwest::Result<PatchGroupResponse>> + Send;
+    ) -> impl Future<Output = crate::Result<PatchGroupResponse>> + Send;
 }
 
 impl Groups for Session {
-    async fn list(&self) -> reqwest::Result<Vec<String>> {
+    async fn list(&self) -> crate::Result<Vec<String>> {
         self.get(PATH, None)
-            .send()
+            .send_with(self)
             .await?
-            .error_for_status()?
-            .json()
+            .check_status()
+            .await?
+            .decode()
             .await
     }
 
@@ -52,13 +54,14 @@ impl Groups for Session {
         .into()
     }
 
-    async fn patch(&self, name: &str, devices: &[Uuid]) -> reqwest::Result<PatchGroupResponse> {
+    async fn patch(&self, name: &str, devices: &[Uuid]) -> crate::Result<PatchGroupResponse> {
         self.patch(format!("{PATH}/{name}/device"), None)
             .json(devices)
-            .send()
+            .send_with(self)
+            .await?
+            .check_status()
             .await?
-            .error_for_status()?
-            .json()
+            .decode()
             .await
     }
 }
@@ -0,0 +1,73 @@
+//! Inventory search API.
+
+use std::num::NonZero;
+
+use crate::dto::{Device, Search};
+use crate::pagination::{DEFAULT_PAGE_SIZE, Pager, Pages, PaginatedIterator};
+use crate::session::Session;
+
+const PATH: &str = "/api/management/v2/inventory/filters";
+
+/// Inventory search API.
+pub trait Inventory {
+    /// Iterate over pages of devices matching the search query.
+    fn search_pages(
+        &self,
+        search: &Search,
+        page_size: Option<NonZero<usize>>,
+    ) -> Pages<'_, '_, Device>;
+
+    /// List devices matching the search query.
+    fn search(
+        &self,
+        search: &Search,
+        page_size: Option<NonZero<usize>>,
+    ) -> PaginatedIterator<'_, '_, Device>;
+
+    /// Collect devices matching the search query into a `Vec`.
+    fn search_collect(
+        &self,
+        search: &Search,
+        page_size: Option<NonZero<usize>>,
+    ) -> impl Future<Output = crate::Result<Vec<Device>>> + Send;
+}
+
+impl Inventory for Session {
+    fn search_pages(
+        &self,
+        search: &Search,
+        page_size: Option<NonZero<usize>>,
+    ) -> Pages<'_, '_, Device> {
+        search_pager(self, search, page_size).into()
+    }
+
+    fn search(
+        &self,
+        search: &Search,
+        page_size: Option<NonZero<usize>>,
+    ) -> PaginatedIterator<'_, '_, Device> {
+        search_pager(self, search, page_size).into()
+    }
+
+    async fn search_collect(
+        &self,
+        search: &Search,
+        page_size: Option<NonZero<usize>>,
+    ) -> crate::Result<Vec<Device>> {
+        search_pager(self, search, page_size).collect().await
+    }
+}
+
+/// Create a pager over devices matching the search query.
+fn search_pager<'session>(
+    session: &'session Session,
+    search: &Search,
+    page_size: Option<NonZero<usize>>,
+) -> Pager<'session, 'static> {
+    Pager::new(
+        session,
+        format!("{PATH}/search").into(),
+        page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+    )
+    .with_search(search.clone())
+}
@@ -0,0 +1,224 @@
+//! Differences between two inventory snapshots.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use uuid::Uuid;
+
+pub use self::attribute_change::AttributeChange;
+pub use self::device_changes::DeviceChanges;
+use crate::dto::{Attribute, Device, Scope};
+
+mod attribute_change;
+mod device_changes;
+
+/// Differences between an old and a new inventory snapshot.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InventoryDiff {
+    added: Vec<Device>,
+    removed: Vec<Device>,
+    changed: Vec<DeviceChanges>,
+}
+
+impl InventoryDiff {
+    /// Returns the devices that are only present in the new snapshot.
+    #[must_use]
+    pub fn added(&self) -> &[Device] {
+        &self.added
+    }
+
+    /// Returns the devices that are only present in the old snapshot.
+    #[must_use]
+    pub fn removed(&self) -> &[Device] {
+        &self.removed
+    }
+
+    /// Returns the attribute changes of devices present in both snapshots.
+    #[must_use]
+    pub fn changed(&self) -> &[DeviceChanges] {
+        &self.changed
+    }
+
+    /// Returns whether the snapshots are identical.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Only retain attribute changes matching the predicate.
+    ///
+    /// Devices without any remaining changes are dropped.
+    #[must_use]
+    pub fn retain_changes<F>(mut self, mut predicate: F) -> Self
+    where
+        F: FnMut(&AttributeChange) -> bool,
+    {
+        self.changed = self
+            .changed
+            .into_iter()
+            .filter_map(|device| device.retain(&mut predicate))
+            .collect();
+        self
+    }
+}
+
+impl Display for InventoryDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for device in &self.added {
+            writeln!(f, "+ {device}")?;
+        }
+
+        for device in &self.removed {
+            writeln!(f, "- {device}")?;
+        }
+
+        for device in &self.changed {
+            Display::fmt(device, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Compute the differences between an old and a new inventory snapshot.
+///
+/// Devices are matched by their ID and attributes by their scope and name. Multiple attributes of
+/// the same scope and name are matched in the order they are listed.
+#[must_use]
+pub fn diff(old: &[Device], new: &[Device]) -> InventoryDiff {
+    let old: BTreeMap<Uuid, &Device> = old.iter().map(|device| (device.id(), device)).collect();
+    let new: BTreeMap<Uuid, &Device> = new.iter().map(|device| (device.id(), device)).collect();
+    let added = new
+        .iter()
+        .filter(|(id, _)| !old.contains_key(id))
+        .map(|(_, device)| (*device).clone())
+        .collect();
+    let removed = old
+        .iter()
+        .filter(|(id, _)| !new.contains_key(id))
+        .map(|(_, device)| (*device).clone())
+        .collect();
+    let changed = old
+        .iter()
+        .filter_map(|(id, old)| new.get(id).map(|new| (old, new)))
+        .filter_map(|(old, new)| DeviceChanges::between(old, new))
+        .collect();
+
+    InventoryDiff {
+        added,
+        removed,
+        changed,
+    }
+}
+
+/// Group the attributes of a device by their scope and name.
+fn attributes(device: &Device) -> BTreeMap<(Scope, &str), Vec<&Attribute>> {
+    let mut attributes: BTreeMap<_, Vec<_>> = BTreeMap::new();
+
+    for attribute in device.attributes() {
+        attributes
+            .entry((attribute.scope(), attribute.name()))
+            .or_default()
+            .push(attribute);
+    }
+
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use std::slice::from_ref;
+
+    use serde_json::{Value, json};
+    use uuid::Uuid;
+
+    use super::{AttributeChange, DeviceChanges, diff};
+    use crate::dto::{Device, Scope};
+
+    const ID: Uuid = Uuid::from_u128(1);
+
+    fn device(id: Uuid, attributes: &[(&str, &str)]) -> Device {
+        let attributes: Vec<Value> = attributes
+            .iter()
+            .map(|(name, value)| json!({ "name": name, "value": value, "scope": "inventory" }))
+            .collect();
+        serde_json::from_value(json!({
+            "id": id,
+            "attributes": attributes,
+            "updated_ts": "2024-01-15T10:23:45Z",
+        }))
+        .unwrap()
+    }
+
+    fn changes(old: &Device, new: &Device) -> Vec<String> {
+        diff(from_ref(old), from_ref(new))
+            .changed()
+            .iter()
+            .flat_map(DeviceChanges::changes)
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn reports_added_and_removed_devices() {
+        let kept = device(ID, &[("foo", "1")]);
+        let removed = device(Uuid::from_u128(2), &[]);
+        let added = device(Uuid::from_u128(3), &[]);
+        let diff = diff(&[kept.clone(), removed.clone()], &[added.clone(), kept]);
+        assert_eq!(diff.added(), [added]);
+        assert_eq!(diff.removed(), [removed]);
+        assert!(diff.changed().is_empty());
+    }
+
+    #[test]
+    fn ignores_unchanged_devices() {
+        let device = device(ID, &[("foo", "1"), ("bar", "2")]);
+        assert!(diff(from_ref(&device), from_ref(&device)).is_empty());
+    }
+
+    #[test]
+    fn reports_attribute_changes() {
+        let old = device(ID, &[("foo", "1"), ("bar", "2"), ("baz", "3")]);
+        let new = device(ID, &[("foo", "1"), ("bar", "4"), ("qux", "5")]);
+        assert_eq!(
+            changes(&old, &new),
+            [
+                "~ inventory:bar: 2 -> 4",
+                "- inventory:baz: 3",
+                "+ inventory:qux: 5",
+            ]
+        );
+    }
+
+    #[test]
+    fn compares_all_values_of_repeated_attributes() {
+        let old = device(ID, &[("foo", "1"), ("foo", "2")]);
+        let new = device(ID, &[("foo", "1"), ("foo", "3"), ("foo", "4")]);
+        assert_eq!(
+            changes(&old, &new),
+            ["~ inventory:foo: 2 -> 3", "+ inventory:foo: 4"]
+        );
+        assert_eq!(
+            changes(&new, &old),
+            ["~ inventory:foo: 3 -> 2", "- inventory:foo: 4"]
+        );
+        assert!(changes(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn retains_matching_changes() {
+        let old = device(ID, &[("foo", "1"), ("bar", "2")]);
+        let new = device(ID, &[("foo", "3"), ("bar", "4")]);
+        let diff =
+            diff(from_ref(&old), from_ref(&new)).retain_changes(|change| change.name() == "foo");
+        assert_eq!(diff.changed().len(), 1);
+        assert!(matches!(
+            diff.changed()[0].changes(),
+            [AttributeChange::Changed { .. }]
+        ));
+        assert_eq!(diff.changed()[0].changes()[0].scope(), Scope::Inventory);
+
+        let diff = super::diff(&[old], &[new]).retain_changes(|_| false);
+        assert!(diff.is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt::Display;
+
+use crate::dto::{Attribute, Scope};
+
+/// A change of a single device attribute.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AttributeChange {
+    /// The attribute was added.
+    Added(Attribute),
+    /// The attribute was removed.
+    Removed(Attribute),
+    /// The attribute's value or description changed.
+    Changed {
+        /// The attribute in the old snapshot.
+        old: Attribute,
+        /// The attribute in the new snapshot.
+        new: Attribute,
+    },
+}
+
+impl AttributeChange {
+    /// Returns the scope of the changed attribute.
+    #[must_use]
+    pub const fn scope(&self) -> Scope {
+        match self {
+            Self::Added(attribute)
+            | Self::Removed(attribute)
+            | Self::Changed { new: attribute, .. } => attribute.scope(),
+        }
+    }
+
+    /// Returns the name of the changed attribute.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Added(attribute)
+            | Self::Removed(attribute)
+            | Self::Changed { new: attribute, .. } => attribute.name(),
+        }
+    }
+}
+
+impl Display for AttributeChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added(attribute) => write!(
+                f,
+                "+ {}:{}: {}",
+                attribute.scope(),
+                attribute.name(),
+                attribute.value_to_string()
+            ),
+            Self::Removed(attribute) => write!(
+                f,
+                "- {}:{}: {}",
+                attribute.scope(),
+                attribute.name(),
+                attribute.value_to_string()
+            ),
+            Self::Changed { old, new } => write!(
+                f,
+                "~ {}:{}: {} -> {}",
+                new.scope(),
+                new.name(),
+                old.value_to_string(),
+                new.value_to_string()
+            ),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt::Display;
+
+use uuid::Uuid;
+
+use super::{AttributeChange, attributes};
+use crate::dto::{Attribute, Device};
+
+/// Attribute changes of a single device.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeviceChanges {
+    device: Device,
+    changes: Vec<AttributeChange>,
+}
+
+impl DeviceChanges {
+    /// Compute the attribute changes between two snapshots of the same device.
+    ///
+    /// Returns `None` if no attributes changed.
+    #[must_use]
+    pub fn between(old: &Device, new: &Device) -> Option<Self> {
+        let old_attributes = attributes(old);
+        let new_attributes = attributes(new);
+        let mut changes = Vec::new();
+
+        for (key, old) in &old_attributes {
+            let new = new_attributes.get(key).map_or(&[][..], Vec::as_slice);
+            changes.extend(compare(old, new));
+        }
+
+        for (key, new) in &new_attributes {
+            if !old_attributes.contains_key(key) {
+                changes.extend(compare(&[], new));
+            }
+        }
+
+        if changes.is_empty() {
+            None
+        } else {
+            Some(Self {
+                device: new.clone(),
+                changes,
+            })
+        }
+    }
+
+    /// Returns the ID of the device.
+    #[must_use]
+    pub const fn id(&self) -> Uuid {
+        self.device.id()
+    }
+
+    /// Returns the device as of the new snapshot.
+    #[must_use]
+    pub const fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Returns the attribute changes.
+    #[must_use]
+    pub fn changes(&self) -> &[AttributeChange] {
+        &self.changes
+    }
+
+    /// Only retain changes matching the predicate, returning `None` if none remain.
+    pub(super) fn retain<F>(mut self, predicate: F) -> Option<Self>
+    where
+        F: FnMut(&AttributeChange) -> bool,
+    {
+        self.changes.retain(predicate);
+
+        if self.changes.is_empty() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+}
+
+/// Compare attributes of the same scope and name pairwise in order.
+fn compare<'a>(
+    old: &'a [&Attribute],
+    new: &'a [&Attribute],
+) -> impl Iterator<Item = AttributeChange> + 'a {
+    (0..old.len().max(new.len())).filter_map(|index| match (old.get(index), new.get(index)) {
+        (Some(old), Some(new)) if old != new => Some(AttributeChange::Changed {
+            old: (*old).clone(),
+            new: (*new).clone(),
+        }),
+        (Some(old), None) => Some(AttributeChange::Removed((*old).clone())),
+        (None, Some(new)) => Some(AttributeChange::Added((*new).clone())),
+        _ => None,
+    })
+}
+
+impl Display for DeviceChanges {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "~ {}", self.device)?;
+
+        for change in &self.changes {
+            writeln!(f, "\t{change}")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+//! Saved inventory filters API.
+
+use std::num::NonZero;
+
+use uuid::Uuid;
+
+use crate::Inventory;
+use crate::dto::{Device, Filter, NewFilter, Predicate, Search};
+use crate::pagination::{DEFAULT_PAGE_SIZE, Pager, PaginatedIterator};
+use crate::session::Session;
+use crate::utils::{RequestBuilderExt, ResponseExt};
+
+const PATH: &str = "/api/management/v2/inventory/filters";
+
+/// Saved inventory filters API.
+pub trait InventoryFilters {
+    /// List all saved filters.
+    fn list(&self, page_size: Option<NonZero<usize>>) -> PaginatedIterator<'_, '_, Filter>;
+
+    /// Collect all saved filters into a `Vec`.
+    fn collect(
+        &self,
+        page_size: Option<NonZero<usize>>,
+    ) -> impl Future<Output = crate::Result<Vec<Filter>>> + Send;
+
+    /// Create a new saved filter, returning its ID if the server reported it.
+    fn create<N>(
+        &self,
+        name: N,
+        terms: &[Predicate],
+    ) -> impl Future<Output = crate::Result<Option<Uuid>>> + Send
+    where
+        N: AsRef<str> + Send + Sync;
+
+    /// Get a saved filter.
+    fn get(&self, id: Uuid) -> impl Future<Output = crate::Result<Filter>> + Send;
+
+    /// Delete a saved filter.
+    fn delete(&self, id: Uuid) -> impl Future<Output = crate::Result<()>> + Send;
+
+    /// Collect the devices matching a saved filter into a `Vec`.
+    fn devices_of(
+        &self,
+        id: Uuid,
+        page_size: Option<NonZero<usize>>,
+    ) -> impl Future<Output = crate::Result<Vec<Device>>> + Send;
+}
+
+impl InventoryFilters for Session {
+    fn list(&self, page_size: Option<NonZero<usize>>) -> PaginatedIterator<'_, '_, Filter> {
+        Pager::new(self, PATH.into(), page_size.unwrap_or(DEFAULT_PAGE_SIZE)).into()
+    }
+
+    async fn collect(&self, page_size: Option<NonZero<usize>>) -> crate::Result<Vec<Filter>> {
+        Pager::new(self, PATH.into(), page_size.unwrap_or(DEFAULT_PAGE_SIZE))
+            .collect()
+            .await
+    }
+
+    async fn create<N>(&self, name: N, terms: &[Predicate]) -> crate::Result<Option<Uuid>>
+    where
+        N: AsRef<str> + Send + Sync,
+    {
+        self.post(PATH, None)
+            .json(&NewFilter::new(name.as_ref(), terms))
+            .send_with(self)
+            .await?
+            .check_status()
+            .await
+            .map(|response| response.created_id())
+    }
+
+    async fn get(&self, id: Uuid) -> crate::Result<Filter> {
+        self.get(format!("{PATH}/{id}"), None)
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .decode()
+            .await
+    }
+
+    async fn delete(&self, id: Uuid) -> crate::Result<()> {
+        self.delete(format!("{PATH}/{id}"), None)
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .ensure_empty()
+            .await
+    }
+
+    async fn devices_of(
+        &self,
+        id: Uuid,
+        page_size: Option<NonZero<usize>>,
+    ) -> crate::Result<Vec<Device>> {
+        let filter = InventoryFilters::get(self, id).await?;
+        self.search_collect(&Search::from(&filter), page_size).await
+    }
+}
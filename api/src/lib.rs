@@ -1,31 +1,46 @@
 //!  Implementation of the Mender server API.
 
-pub use reqwest::{Certificate, Error, Result, StatusCode};
+pub use reqwest::header::{HeaderName, HeaderValue};
+pub use reqwest::{Certificate, Identity, Proxy, StatusCode};
 
-pub use self::artifacts::Artifacts;
-pub use self::client::Client;
+pub use self::artifacts::{ArtifactGeneration, ArtifactUpload, Artifacts, DownloadError};
+pub use self::client::{Client, ClientBuilder};
 pub use self::deployments::Deployments;
+pub use self::device_auth::DeviceAuth;
 pub use self::device_proxy::DeviceProxy;
 pub use self::devices::Devices;
+pub use self::error::{ApiError, Error, Result};
 pub use self::groups::Groups;
+pub use self::inventory::Inventory;
+pub use self::inventory_filters::InventoryFilters;
 pub use self::login::Login;
 pub use self::pagination::{Pager, Pages, PaginatedIterator};
+pub use self::personal_access_tokens::PersonalAccessTokens;
 pub use self::releases::Releases;
+pub use self::retry::RetryPolicy;
 pub use self::session::Session;
 pub use self::tags::Tags;
 pub use self::utils::PemCertificate;
 
+pub mod archive;
 mod artifacts;
 mod client;
 mod config_file;
 mod deployments;
+mod device_auth;
 mod device_proxy;
 mod devices;
 pub mod dto;
+mod error;
 mod groups;
+mod inventory;
+pub mod inventory_diff;
+mod inventory_filters;
 mod login;
 mod pagination;
+mod personal_access_tokens;
 mod releases;
+mod retry;
 mod session;
 mod tags;
 mod utils;
@@ -1,47 +1,201 @@
-use crate::Client;
+use serde::Serialize;
+
 use crate::session::Session;
+use crate::{Client, Error};
 
 const PATH: &str = "/api/management/v1/useradm/auth/login";
 
+/// Errors by which useradm rejects a login with HTTP 401 due to a missing or invalid second factor.
+const SECOND_FACTOR_ERRORS: [&str; 2] = ["2fa required", "2fa token invalid"];
+
 /// User login API.
 pub trait Login {
     /// Log in to the Mender server with the given username and password.
     ///
+    /// The returned session keeps the credentials to log in again once its token expires,
+    /// unless they are discarded with [`Session::without_credentials`].
+    ///
     /// # Returns
     ///
     /// Returns a future that resolves to a [`Session`] object on success.
     ///
     /// # Errors
     ///
-    /// If the login fails, the future will resolve to a [`reqwest::Error`].
+    /// If the login fails, the future will resolve to a [`Error`](crate::Error).
+    /// If the account requires two-factor authentication, this is
+    /// [`Error::SecondFactorRequired`](crate::Error::SecondFactorRequired).
     fn login<U, P>(
         self,
         user_name: U,
         password: P,
-    ) -> impl Future<Output = reqwest::Result<Session>> + Send
+    ) -> impl Future<Output = crate::Result<Session>> + Send
     where
         U: AsRef<str> + Send,
         P: AsRef<str> + Send;
+
+    /// Log in to the Mender server with the given username, password and TOTP code
+    /// for two-factor authentication.
+    ///
+    /// Since the code is only valid once, the returned session cannot log in again
+    /// once its token expires.
+    ///
+    /// # Returns
+    ///
+    /// Returns a future that resolves to a [`Session`] object on success.
+    ///
+    /// # Errors
+    ///
+    /// If the login fails, the future will resolve to a [`Error`](crate::Error).
+    /// If the code was rejected, this is
+    /// [`Error::SecondFactorRequired`](crate::Error::SecondFactorRequired).
+    fn login_with_totp<U, P, C>(
+        self,
+        user_name: U,
+        password: P,
+        code: C,
+    ) -> impl Future<Output = crate::Result<Session>> + Send
+    where
+        U: AsRef<str> + Send,
+        P: AsRef<str> + Send,
+        C: AsRef<str> + Send;
 }
 
 impl Login for Client {
-    async fn login<U, P>(self, user_name: U, password: P) -> reqwest::Result<Session>
+    async fn login<U, P>(self, user_name: U, password: P) -> crate::Result<Session>
     where
         U: AsRef<str> + Send,
         P: AsRef<str> + Send,
     {
-        self.client
-            .post({
-                let mut url = self.base_url.clone();
-                url.set_path(PATH);
-                url
-            })
-            .basic_auth(user_name.as_ref(), Some(password.as_ref()))
-            .send()
-            .await?
-            .error_for_status()?
-            .text()
-            .await
-            .map(|bearer_token| Session::new(self.client, self.base_url, bearer_token))
+        let bearer_token = self
+            .request_token(user_name.as_ref(), password.as_ref(), None)
+            .await?;
+        Ok(
+            Session::new(self, bearer_token)
+                .with_credentials(user_name.as_ref(), password.as_ref()),
+        )
+    }
+
+    async fn login_with_totp<U, P, C>(
+        self,
+        user_name: U,
+        password: P,
+        code: C,
+    ) -> crate::Result<Session>
+    where
+        U: AsRef<str> + Send,
+        P: AsRef<str> + Send,
+        C: AsRef<str> + Send,
+    {
+        let bearer_token = self
+            .request_token(user_name.as_ref(), password.as_ref(), Some(code.as_ref()))
+            .await?;
+        Ok(Session::new(self, bearer_token))
+    }
+}
+
+impl Client {
+    /// Request a new bearer token with the given username, password and optional TOTP code.
+    pub(crate) async fn request_token(
+        &self,
+        user_name: &str,
+        password: &str,
+        token2fa: Option<&str>,
+    ) -> crate::Result<String> {
+        let mut url = self.base_url.clone();
+        url.set_path(PATH);
+        let request = self.http.post(url).basic_auth(user_name, Some(password));
+
+        let response = if let Some(token2fa) = token2fa {
+            // A TOTP code is only valid once, so a login with it must not be replayed.
+            request.json(&SecondFactor { token2fa }).send().await?
+        } else {
+            self.retry_policy.send(request, true).await?
+        };
+
+        if !response.status().is_success() {
+            return Err(login_error(Error::from_response(response).await));
+        }
+
+        response.text().await.map_err(Into::into)
+    }
+}
+
+/// Login request body carrying the second factor.
+#[derive(Debug, Serialize)]
+struct SecondFactor<'code> {
+    token2fa: &'code str,
+}
+
+/// Map a rejected login to [`Error::SecondFactorRequired`] if useradm asked for a second factor.
+fn login_error(error: Error) -> Error {
+    match error {
+        Error::Unauthorized(error)
+            if error.message().is_some_and(|message| {
+                SECOND_FACTOR_ERRORS
+                    .iter()
+                    .any(|expected| message.trim().eq_ignore_ascii_case(expected))
+            }) =>
+        {
+            Error::SecondFactorRequired(error)
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+
+    use super::login_error;
+    use crate::Error;
+    use crate::error::ApiError;
+
+    fn rejected(status: StatusCode, body: &str) -> Error {
+        login_error(ApiError::new(status, None, body.as_bytes()).into())
+    }
+
+    #[test]
+    fn maps_missing_second_factor() {
+        assert!(matches!(
+            rejected(
+                StatusCode::UNAUTHORIZED,
+                r#"{"error": "2fa required", "request_id": "1"}"#
+            ),
+            Error::SecondFactorRequired(_)
+        ));
+    }
+
+    #[test]
+    fn maps_invalid_second_factor() {
+        assert!(matches!(
+            rejected(
+                StatusCode::UNAUTHORIZED,
+                r#"{"error": "2FA token invalid"}"#
+            ),
+            Error::SecondFactorRequired(_)
+        ));
+    }
+
+    #[test]
+    fn keeps_invalid_credentials() {
+        assert!(matches!(
+            rejected(StatusCode::UNAUTHORIZED, r#"{"error": "unauthorized"}"#),
+            Error::Unauthorized(_)
+        ));
+    }
+
+    #[test]
+    fn ignores_mentions_of_second_factor() {
+        assert!(matches!(
+            rejected(
+                StatusCode::UNAUTHORIZED,
+                r#"{"error": "user has no 2fa required for this tenant"}"#
+            ),
+            Error::Unauthorized(_)
+        ));
+        assert!(matches!(
+            rejected(StatusCode::BAD_REQUEST, r#"{"error": "2fa required"}"#),
+            Error::Validation(_)
+        ));
     }
 }
@@ -1,15 +1,19 @@
 use std::borrow::Cow;
 use std::num::NonZero;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::Session;
+use crate::dto::Search;
+use crate::utils::{RequestBuilderExt, ResponseExt};
 
 /// Generic pager.
 #[derive(Debug, Clone)]
 pub struct Pager<'session, 'path> {
     session: &'session Session,
     path: Cow<'path, str>,
+    query: Option<Cow<'path, str>>,
+    search: Option<Search>,
     page_size: NonZero<usize>,
 }
 
@@ -24,9 +28,28 @@ impl<'session, 'path> Pager<'session, 'path> {
         Self {
             session,
             path,
+            query: None,
+            search: None,
             page_size,
         }
     }
+
+    /// Set an additional query string to send along with each page request.
+    #[must_use]
+    pub fn with_query(mut self, query: Cow<'path, str>) -> Self {
+        self.query.replace(query);
+        self
+    }
+
+    /// Set a search query to send along with each page request.
+    ///
+    /// Pages are then requested via `POST` with the search query as body and the paging
+    /// parameters added to it instead of the query string.
+    #[must_use]
+    pub fn with_search(mut self, search: Search) -> Self {
+        self.search.replace(search);
+        self
+    }
 }
 
 impl Pager<'_, '_> {
@@ -40,20 +63,41 @@ impl Pager<'_, '_> {
     ///
     /// # Errors
     ///
-    /// Returns a [`reqwest::Error`] if the request fails or the response cannot be deserialized.
-    pub async fn page<T>(&self, page_no: NonZero<usize>) -> reqwest::Result<Vec<T>>
+    /// Returns a [`Error`](crate::Error) if the request fails or the response cannot be deserialized.
+    pub async fn page<T>(&self, page_no: NonZero<usize>) -> crate::Result<Vec<T>>
     where
         for<'deserialize> T: Deserialize<'deserialize>,
     {
+        if let Some(search) = &self.search {
+            return self
+                .session
+                .post(self.path.as_ref(), self.query.as_deref())
+                .json(&SearchPage {
+                    search,
+                    per_page: self.page_size,
+                    page: page_no,
+                })
+                .send_idempotent_with(self.session)
+                .await?
+                .check_status()
+                .await?
+                .decode()
+                .await;
+        }
+
+        let paging = format!("per_page={}&page={page_no}", self.page_size);
+        let query = match &self.query {
+            Some(query) => format!("{query}&{paging}"),
+            None => paging,
+        };
+
         self.session
-            .get(
-                self.path.as_ref(),
-                format!("per_page={}&page={page_no}", self.page_size).as_str(),
-            )
-            .send()
+            .get(self.path.as_ref(), query.as_str())
+            .send_with(self.session)
             .await?
-            .error_for_status()?
-            .json()
+            .check_status()
+            .await?
+            .decode()
             .await
     }
 
@@ -61,8 +105,8 @@ impl Pager<'_, '_> {
     ///
     /// # Errors
     ///
-    /// Returns a [`reqwest::Error`] if any of the page requests fail.
-    pub async fn collect<T>(&self) -> reqwest::Result<Vec<T>>
+    /// Returns a [`Error`](crate::Error) if any of the page requests fail.
+    pub async fn collect<T>(&self) -> crate::Result<Vec<T>>
     where
         for<'deserialize> T: Deserialize<'deserialize>,
     {
@@ -81,3 +125,12 @@ impl Pager<'_, '_> {
         Ok(devices)
     }
 }
+
+/// Body of a page request of a search query.
+#[derive(Serialize)]
+struct SearchPage<'search> {
+    #[serde(flatten)]
+    search: &'search Search,
+    per_page: NonZero<usize>,
+    page: NonZero<usize>,
+}
@@ -32,7 +32,7 @@ where
     for<'deserialize> T: Deserialize<'deserialize> + Send + Sync,
 {
     /// Return the next page.
-    pub async fn next(&mut self) -> Option<reqwest::Result<Vec<T>>> {
+    pub async fn next(&mut self) -> Option<crate::Result<Vec<T>>> {
         if self.done {
             return None;
         }
@@ -30,7 +30,7 @@ where
     for<'deserialize> T: Deserialize<'deserialize> + Send + Sync,
 {
     /// Return the next item in the iterator, fetching a new page if necessary.
-    pub async fn next(&mut self) -> Option<reqwest::Result<T>> {
+    pub async fn next(&mut self) -> Option<crate::Result<T>> {
         if let Some(item) = self.current_page.as_mut().and_then(Iterator::next) {
             return Some(Ok(item));
         }
@@ -50,8 +50,8 @@ where
     ///
     /// # Errors
     ///
-    /// Returns a [`reqwest::Error`] if any of the requests fail or the responses cannot be deserialized.
-    pub async fn collect(&mut self) -> reqwest::Result<Vec<T>> {
+    /// Returns a [`Error`](crate::Error) if any of the requests fail or the responses cannot be deserialized.
+    pub async fn collect(&mut self) -> crate::Result<Vec<T>> {
         let mut results = Vec::new();
 
         while let Some(result) = self.next().await {
@@ -0,0 +1,73 @@
+//! Personal access tokens API.
+
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::dto::{NewPersonalAccessToken, PersonalAccessToken};
+use crate::session::Session;
+use crate::utils::{RequestBuilderExt, ResponseExt};
+
+const PATH: &str = "/api/management/v1/useradm/settings/tokens";
+
+/// Manage the personal access tokens of the logged-in user.
+pub trait PersonalAccessTokens {
+    /// List the personal access tokens.
+    fn list(&self) -> impl Future<Output = crate::Result<Vec<PersonalAccessToken>>> + Send;
+
+    /// Create a new personal access token that expires after the given duration.
+    ///
+    /// # Returns
+    ///
+    /// Returns the token, which cannot be retrieved again later.
+    fn create<N>(
+        &self,
+        name: N,
+        expires_in: Duration,
+    ) -> impl Future<Output = crate::Result<String>> + Send
+    where
+        N: AsRef<str> + Send + Sync;
+
+    /// Revoke a personal access token.
+    fn revoke(&self, id: Uuid) -> impl Future<Output = crate::Result<()>> + Send;
+}
+
+impl PersonalAccessTokens for Session {
+    async fn list(&self) -> crate::Result<Vec<PersonalAccessToken>> {
+        self.get(PATH, None)
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .decode()
+            .await
+    }
+
+    async fn create<N>(&self, name: N, expires_in: Duration) -> crate::Result<String>
+    where
+        N: AsRef<str> + Send + Sync,
+    {
+        self.post(PATH, None)
+            .json(&NewPersonalAccessToken::new(
+                name.as_ref(),
+                expires_in.as_secs(),
+            ))
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .text()
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn revoke(&self, id: Uuid) -> crate::Result<()> {
+        self.delete(format!("{PATH}/{id}"), None)
+            .send_with(self)
+            .await?
+            .check_status()
+            .await?
+            .ensure_empty()
+            .await
+    }
+}
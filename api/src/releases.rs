@@ -15,7 +15,7 @@ pub trait Releases {
     fn collect(
         &self,
         page_size: Option<NonZero<usize>>,
-    ) -> impl Future<Output = reqwest::Result<Vec<Release>>> + Send;
+    ) -> impl Future<Output = crate::Result<Vec<Release>>> + Send;
 }
 
 impl Releases for Session {
@@ -23,7 +23,7 @@ impl Releases for Session {
         Pager::new(self, PATH.into(), page_size.unwrap_or(DEFAULT_PAGE_SIZE)).into()
     }
 
-    async fn collect(&self, page_size: Option<NonZero<usize>>) -> reqwest::Result<Vec<Release>> {
+    async fn collect(&self, page_size: Option<NonZero<usize>>) -> crate::Result<Vec<Release>> {
         Pager::new(self, PATH.into(), page_size.unwrap_or(DEFAULT_PAGE_SIZE))
             .collect()
             .await
@@ -0,0 +1,321 @@
+//! Retrying of failed requests.
+
+use std::hash::{BuildHasher, RandomState};
+use std::num::NonZero;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use log::warn;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Client, Method, Request, RequestBuilder, Response, StatusCode};
+
+const DEFAULT_MAX_ATTEMPTS: NonZero<u32> =
+    NonZero::new(4).expect("Default attempts should be non-zero.");
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Policy for retrying failed requests with exponential backoff.
+///
+/// Only requests that are safe to repeat are retried, i.e. requests with idempotent methods,
+/// that failed due to a transport error or a transient server error (HTTP 429, 502, 503 or 504).
+/// If the server sends a `Retry-After` header along with HTTP 429 or 503, it is honoured,
+/// up to the maximum backoff.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: NonZero<u32>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+}
+
+impl RetryPolicy {
+    /// Creates a new retry policy with the given maximum number of attempts.
+    #[must_use]
+    pub const fn new(max_attempts: NonZero<u32>) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            jitter: true,
+        }
+    }
+
+    /// Creates a retry policy that never retries.
+    #[must_use]
+    pub const fn never() -> Self {
+        Self::new(NonZero::<u32>::MIN)
+    }
+
+    /// Set the backoff before the first retry, which doubles with each subsequent retry.
+    #[must_use]
+    pub const fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Set the maximum backoff between retries.
+    #[must_use]
+    pub const fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Set whether to randomize the backoff between zero and its computed value.
+    #[must_use]
+    pub const fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns the maximum number of attempts.
+    #[must_use]
+    pub const fn max_attempts(&self) -> NonZero<u32> {
+        self.max_attempts
+    }
+
+    /// Send the request, retrying it if its method is idempotent or `idempotent` is set.
+    ///
+    /// Setting `idempotent` must only be done for requests without side effects,
+    /// such as searches via `POST`.
+    pub(crate) async fn send(
+        &self,
+        request: RequestBuilder,
+        idempotent: bool,
+    ) -> crate::Result<Response> {
+        let (client, request) = request.build_split();
+        self.execute(&client, request?, idempotent).await
+    }
+
+    /// Execute the request with the given client, retrying it if its method is idempotent or
+    /// `idempotent` is set.
+    pub(crate) async fn execute(
+        &self,
+        client: &Client,
+        request: Request,
+        idempotent: bool,
+    ) -> crate::Result<Response> {
+        let retryable = idempotent || is_idempotent(request.method());
+
+        for attempt in 1..self.max_attempts.get() {
+            let Some(retry) = request.try_clone().filter(|_| retryable) else {
+                break;
+            };
+
+            let delay = match client.execute(retry).await {
+                Ok(response) if is_transient(response.status()) => {
+                    warn!(
+                        "Attempt {attempt} of {} {} failed: {}",
+                        request.method(),
+                        request.url(),
+                        response.status()
+                    );
+                    self.delay(attempt, retry_after(&response))
+                }
+                Ok(response) => return Ok(response),
+                Err(error) => {
+                    warn!(
+                        "Attempt {attempt} of {} {} failed: {error}",
+                        request.method(),
+                        request.url()
+                    );
+                    self.backoff(attempt)
+                }
+            };
+
+            tokio::time::sleep(delay).await;
+        }
+
+        Ok(client.execute(request).await?)
+    }
+
+    /// Compute the delay before the retry following the given attempt.
+    ///
+    /// A delay requested by the server is preferred, but capped at the maximum backoff.
+    fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        retry_after.map_or_else(
+            || self.backoff(attempt),
+            |retry_after| retry_after.min(self.max_backoff),
+        )
+    }
+
+    /// Compute the backoff before the retry following the given attempt.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff);
+
+        if self.jitter {
+            backoff.mul_f64(random_fraction())
+        } else {
+            backoff
+        }
+    }
+}
+
+/// Return a pseudo-random number in the range `[0, 1)`.
+///
+/// The randomly keyed hasher of the standard library is good enough for jitter.
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().hash_one(());
+    // Use the upper 52 bits as the mantissa of a number in `[1, 2)`.
+    f64::from_bits(0x3FF0_0000_0000_0000 | (bits >> 12)) - 1.0
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ATTEMPTS)
+    }
+}
+
+/// Returns whether requests with the given method may be repeated without additional effects.
+fn is_idempotent(method: &Method) -> bool {
+    [
+        Method::GET,
+        Method::HEAD,
+        Method::OPTIONS,
+        Method::PUT,
+        Method::DELETE,
+    ]
+    .contains(method)
+}
+
+/// Returns whether the status signals a transient failure.
+const fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Parse the `Retry-After` header of HTTP 429 and 503 responses.
+fn retry_after(response: &Response) -> Option<Duration> {
+    if !matches!(
+        response.status(),
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+    ) {
+        return None;
+    }
+
+    parse_retry_after(
+        response.headers().get(RETRY_AFTER)?.to_str().ok()?,
+        Utc::now(),
+    )
+}
+
+/// Parse the value of a `Retry-After` header relative to the given time.
+///
+/// The header may either contain a number of seconds or an HTTP date.
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+
+    value.parse().map(Duration::from_secs).ok().or_else(|| {
+        DateTime::parse_from_rfc2822(value)
+            .ok()
+            .map(|date| (date.to_utc() - now).to_std().unwrap_or_default())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+    use std::time::Duration;
+
+    use chrono::{DateTime, Utc};
+
+    use super::{RetryPolicy, parse_retry_after, random_fraction};
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy::new(NonZero::new(5).expect("5 is non-zero"))
+            .with_initial_backoff(Duration::from_secs(1))
+            .with_max_backoff(Duration::from_secs(5))
+            .with_jitter(false)
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .expect("valid date")
+            .to_utc()
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let policy = policy();
+        let backoffs: Vec<_> = (1..=5).map(|attempt| policy.backoff(attempt)).collect();
+        assert_eq!(backoffs, [1, 2, 4, 5, 5].map(Duration::from_secs).to_vec());
+    }
+
+    #[test]
+    fn backoff_does_not_overflow() {
+        assert_eq!(policy().backoff(u32::MAX), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn jitter_stays_within_backoff() {
+        let policy = policy().with_jitter(true);
+
+        for attempt in 1..=5 {
+            assert!(policy.backoff(attempt) <= policy.with_jitter(false).backoff(attempt));
+        }
+    }
+
+    #[test]
+    fn jitter_varies() {
+        let fractions: Vec<_> = (0..100).map(|_| random_fraction()).collect();
+        assert!(
+            fractions
+                .iter()
+                .all(|fraction| (0.0..1.0).contains(fraction))
+        );
+        assert!(
+            fractions
+                .iter()
+                .any(|fraction| fraction.to_bits() != fractions[0].to_bits())
+        );
+    }
+
+    #[test]
+    fn retry_after_is_preferred_over_backoff() {
+        assert_eq!(
+            policy().delay(1, Some(Duration::from_secs(3))),
+            Duration::from_secs(3)
+        );
+        assert_eq!(policy().delay(2, None), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn retry_after_is_capped_at_max_backoff() {
+        assert_eq!(
+            policy().delay(1, Some(Duration::from_hours(24))),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn parses_retry_after_seconds() {
+        assert_eq!(
+            parse_retry_after(" 120 ", now()),
+            Some(Duration::from_mins(2))
+        );
+    }
+
+    #[test]
+    fn parses_retry_after_date() {
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now()),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now()),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_retry_after() {
+        assert_eq!(parse_retry_after("soon", now()), None);
+    }
+}
@@ -1,21 +1,182 @@
-use reqwest::{Client, RequestBuilder, Url};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use log::warn;
+use reqwest::header::{AUTHORIZATION, HeaderValue};
+use reqwest::{Request, RequestBuilder, Response, StatusCode, Url};
+
+use self::bearer_token::BearerToken;
+use self::credentials::Credentials;
+use crate::{Client, RetryPolicy};
+
+mod bearer_token;
+mod credentials;
+
+/// Time before the token's expiry at which to log in again.
+const RELOGIN_MARGIN: TimeDelta = TimeDelta::minutes(1);
 
 /// A session on the Mender server API.
+///
+/// If the session was created by [`Login::login`](crate::Login::login), it keeps the credentials
+/// to transparently log in again shortly before the token expires or after the server rejected it.
 #[derive(Clone, Debug)]
 pub struct Session {
     client: Client,
-    base_url: Url,
-    bearer_token: String,
+    bearer_token: Arc<RwLock<BearerToken>>,
+    credentials: Option<Credentials>,
 }
 
 impl Session {
     /// Create a new `Endpoint` with the specified Mender server.
-    pub(crate) const fn new(client: Client, base_url: Url, bearer_token: String) -> Self {
+    pub(crate) fn new(client: Client, bearer_token: String) -> Self {
         Self {
             client,
-            base_url,
-            bearer_token,
+            bearer_token: Arc::new(RwLock::new(bearer_token.into())),
+            credentials: None,
+        }
+    }
+
+    /// Create a session from a personal access token, skipping the login.
+    #[must_use]
+    pub fn from_token<T>(client: Client, token: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::new(client, token.into())
+    }
+
+    /// Set the credentials to log in again with when the token expires or is rejected.
+    #[must_use]
+    pub fn with_credentials<U, P>(mut self, username: U, password: P) -> Self
+    where
+        U: Into<String>,
+        P: Into<String>,
+    {
+        self.credentials
+            .replace(Credentials::new(username.into(), password.into()));
+        self
+    }
+
+    /// Discard the credentials, so that the session is no longer renewed on expiry.
+    #[must_use]
+    pub fn without_credentials(mut self) -> Self {
+        self.credentials.take();
+        self
+    }
+
+    /// Set the policy for retrying failed requests.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.client.retry_policy = retry_policy;
+        self
+    }
+
+    /// Return the policy for retrying failed requests.
+    #[must_use]
+    pub const fn retry_policy(&self) -> &RetryPolicy {
+        &self.client.retry_policy
+    }
+
+    /// Return the base URL of the Mender server.
+    #[must_use]
+    pub const fn base_url(&self) -> &Url {
+        &self.client.base_url
+    }
+
+    /// Return the username of the credentials kept to log in again, if any.
+    #[must_use]
+    pub fn username(&self) -> Option<&str> {
+        self.credentials.as_ref().map(Credentials::username)
+    }
+
+    /// Return whether the server rejected the current bearer token and the session could not
+    /// log in again, e.g. because it has no credentials or they are no longer valid.
+    #[must_use]
+    pub fn token_rejected(&self) -> bool {
+        self.bearer_token
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_rejected()
+    }
+
+    /// Return the current bearer token.
+    #[must_use]
+    pub fn bearer_token(&self) -> String {
+        self.bearer_token
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_str()
+            .to_owned()
+    }
+
+    /// Return the time at which the current bearer token expires, if known.
+    #[must_use]
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.bearer_token
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .expires_at()
+    }
+
+    /// Return the underlying HTTP client.
+    pub(crate) const fn client(&self) -> &reqwest::Client {
+        &self.client.http
+    }
+
+    /// Send the request, retrying it according to the retry policy.
+    ///
+    /// If credentials are available, log in again before the token expires
+    /// or if the server rejected it. The request is then repeated, unless
+    /// its body is a stream that cannot be replayed.
+    pub(crate) async fn send(
+        &self,
+        request: RequestBuilder,
+        idempotent: bool,
+    ) -> crate::Result<Response> {
+        if self
+            .expires_at()
+            .is_some_and(|expires_at| expires_at - RELOGIN_MARGIN <= Utc::now())
+        {
+            self.relogin().await?;
         }
+
+        let (client, request) = request.build_split();
+        let mut request = request?;
+        self.authorize(&mut request);
+        let retry = request.try_clone();
+        let response = self
+            .retry_policy()
+            .execute(&client, request, idempotent)
+            .await?;
+
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        if self.credentials.is_none() {
+            self.reject_token();
+            return Ok(response);
+        }
+
+        warn!("Bearer token was rejected, logging in again.");
+        self.relogin().await.inspect_err(|_| self.reject_token())?;
+
+        // Streaming bodies cannot be replayed, but the renewed token serves subsequent requests.
+        let Some(mut retry) = retry else {
+            return Ok(response);
+        };
+
+        self.authorize(&mut retry);
+        let response = self
+            .retry_policy()
+            .execute(&client, retry, idempotent)
+            .await?;
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            self.reject_token();
+        }
+
+        Ok(response)
     }
 
     /// Make a GET request.
@@ -24,9 +185,9 @@ impl Session {
         P: AsRef<str>,
         Q: Into<Option<&'q str>>,
     {
-        self.client
+        self.client()
             .get(self.format_url(path, query))
-            .bearer_auth(&self.bearer_token)
+            .bearer_auth(self.bearer_token())
     }
 
     /// Make a POST request.
@@ -35,9 +196,9 @@ impl Session {
         P: AsRef<str>,
         Q: Into<Option<&'q str>>,
     {
-        self.client
+        self.client()
             .post(self.format_url(path, query))
-            .bearer_auth(&self.bearer_token)
+            .bearer_auth(self.bearer_token())
     }
 
     /// Make a PATCH request.
@@ -46,9 +207,9 @@ impl Session {
         P: AsRef<str>,
         Q: Into<Option<&'q str>>,
     {
-        self.client
+        self.client()
             .patch(self.format_url(path, query))
-            .bearer_auth(&self.bearer_token)
+            .bearer_auth(self.bearer_token())
     }
 
     /// Make a PUT request.
@@ -57,9 +218,9 @@ impl Session {
         P: AsRef<str>,
         Q: Into<Option<&'q str>>,
     {
-        self.client
+        self.client()
             .put(self.format_url(path, query))
-            .bearer_auth(&self.bearer_token)
+            .bearer_auth(self.bearer_token())
     }
 
     /// Make a DELETE request.
@@ -68,9 +229,42 @@ impl Session {
         P: AsRef<str>,
         Q: Into<Option<&'q str>>,
     {
-        self.client
+        self.client()
             .delete(self.format_url(path, query))
-            .bearer_auth(&self.bearer_token)
+            .bearer_auth(self.bearer_token())
+    }
+
+    /// Log in again with the stored credentials, if any.
+    async fn relogin(&self) -> crate::Result<()> {
+        let Some(credentials) = &self.credentials else {
+            return Ok(());
+        };
+
+        let bearer_token = self
+            .client
+            .request_token(credentials.username(), credentials.password(), None)
+            .await?;
+        *self
+            .bearer_token
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = bearer_token.into();
+        Ok(())
+    }
+
+    /// Mark the current bearer token as rejected by the server.
+    fn reject_token(&self) {
+        self.bearer_token
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .reject();
+    }
+
+    /// Set the authorization header of the request to the current bearer token.
+    fn authorize(&self, request: &mut Request) {
+        if let Ok(mut value) = HeaderValue::try_from(format!("Bearer {}", self.bearer_token())) {
+            value.set_sensitive(true);
+            request.headers_mut().insert(AUTHORIZATION, value);
+        }
     }
 
     /// Return the URL to the specified path on the Mender server.
@@ -80,7 +274,7 @@ impl Session {
         P: AsRef<str>,
         Q: Into<Option<&'q str>>,
     {
-        let mut url = self.base_url.clone();
+        let mut url = self.client.base_url.clone();
         url.set_path(path.as_ref());
 
         if let Some(query) = query.into() {
@@ -0,0 +1,64 @@
+use base64::Engine;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// A bearer token along with its expiry as claimed by the JWT.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BearerToken {
+    token: String,
+    expires_at: Option<DateTime<Utc>>,
+    rejected: bool,
+}
+
+impl BearerToken {
+    /// Return the raw token.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.token
+    }
+
+    /// Return the time of expiry, if the token is a JWT with an `exp` claim.
+    #[must_use]
+    pub const fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+    }
+
+    /// Return whether the server rejected the token.
+    #[must_use]
+    pub const fn is_rejected(&self) -> bool {
+        self.rejected
+    }
+
+    /// Mark the token as rejected by the server.
+    pub const fn reject(&mut self) {
+        self.rejected = true;
+    }
+}
+
+impl From<String> for BearerToken {
+    fn from(token: String) -> Self {
+        let expires_at = token
+            .split('.')
+            .nth(1)
+            .and_then(|payload| {
+                BASE64_URL_SAFE_NO_PAD
+                    .decode(payload.trim_end_matches('='))
+                    .ok()
+            })
+            .and_then(|payload| serde_json::from_slice::<Claims>(&payload).ok())
+            .and_then(|claims| claims.exp)
+            .and_then(|exp| DateTime::from_timestamp(exp, 0));
+        Self {
+            token,
+            expires_at,
+            rejected: false,
+        }
+    }
+}
+
+/// The JWT claims relevant to us.
+#[derive(Debug, Deserialize)]
+struct Claims {
+    exp: Option<i64>,
+}
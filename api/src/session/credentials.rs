@@ -0,0 +1,37 @@
+use std::fmt::{self, Debug, Formatter};
+
+/// Credentials used to log in again once a session's token expires.
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct Credentials {
+    username: String,
+    password: String,
+}
+
+impl Credentials {
+    /// Create new credentials.
+    #[must_use]
+    pub const fn new(username: String, password: String) -> Self {
+        Self { username, password }
+    }
+
+    /// Return the username.
+    #[must_use]
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Return the password.
+    #[must_use]
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+}
+
+impl Debug for Credentials {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .field("password", &"********")
+            .finish()
+    }
+}
@@ -2,52 +2,50 @@ use uuid::Uuid;
 
 use crate::dto::Tag;
 use crate::session::Session;
-use crate::utils::ResponseExt;
+use crate::utils::{RequestBuilderExt, ResponseExt};
 
 const PATH: &str = "/api/management/v1/inventory/devices";
 
 /// Manage device tags.
 pub trait Tags {
     /// Add tags to the specified device.
-    fn add(
-        &self,
-        device_id: Uuid,
-        tags: &[Tag],
-    ) -> impl Future<Output = reqwest::Result<()>> + Send;
+    fn add(&self, device_id: Uuid, tags: &[Tag]) -> impl Future<Output = crate::Result<()>> + Send;
 
     /// Assign tags to the specified device.
     fn assign(
         &self,
         device_id: Uuid,
         tags: &[Tag],
-    ) -> impl Future<Output = reqwest::Result<()>> + Send;
+    ) -> impl Future<Output = crate::Result<()>> + Send;
 
     /// Clear tags of the specified device.
-    fn clear(&self, device_id: Uuid) -> impl Future<Output = reqwest::Result<()>> + Send;
+    fn clear(&self, device_id: Uuid) -> impl Future<Output = crate::Result<()>> + Send;
 }
 
 impl Tags for Session {
-    async fn add(&self, device_id: Uuid, tags: &[Tag]) -> reqwest::Result<()> {
+    async fn add(&self, device_id: Uuid, tags: &[Tag]) -> crate::Result<()> {
         self.patch(format!("{PATH}/{device_id}/tags"), None)
             .json(tags)
-            .send()
+            .send_with(self)
+            .await?
+            .check_status()
             .await?
-            .error_for_status()?
             .ensure_empty()
             .await
     }
 
-    async fn assign(&self, device_id: Uuid, tags: &[Tag]) -> reqwest::Result<()> {
+    async fn assign(&self, device_id: Uuid, tags: &[Tag]) -> crate::Result<()> {
         self.put(format!("{PATH}/{device_id}/tags"), None)
             .json(tags)
-            .send()
+            .send_with(self)
+            .await?
+            .check_status()
             .await?
-            .error_for_status()?
             .ensure_empty()
             .await
     }
 
-    async fn clear(&self, device_id: Uuid) -> reqwest::Result<()> {
+    async fn clear(&self, device_id: Uuid) -> crate::Result<()> {
         self.assign(device_id, &[]).await
     }
 }
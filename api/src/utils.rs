@@ -3,10 +3,12 @@
 use std::fmt::Display;
 
 pub use self::pem_certificate::PemCertificate;
+pub use self::request_builder_ext::RequestBuilderExt;
 pub use self::response_ext::ResponseExt;
 
 pub mod as_str;
 mod pem_certificate;
+mod request_builder_ext;
 mod response_ext;
 
 /// Display a slice of items, formatting them as a comma-separated list enclosed in square brackets.
@@ -36,3 +38,15 @@ where
         write!(f, "]")
     }
 }
+
+/// Join the string representations of the items with the given separator.
+pub fn join<T>(items: &[T], separator: &str) -> String
+where
+    T: Display,
+{
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
@@ -0,0 +1,26 @@
+use reqwest::{RequestBuilder, Response};
+
+use crate::Session;
+
+pub trait RequestBuilderExt {
+    /// Send the request via the session, retrying it if its method is idempotent.
+    fn send_with(self, session: &Session) -> impl Future<Output = crate::Result<Response>> + Send;
+
+    /// Send the request via the session, retrying it regardless of its method.
+    ///
+    /// This must only be used for requests without side effects, such as searches via `POST`.
+    fn send_idempotent_with(
+        self,
+        session: &Session,
+    ) -> impl Future<Output = crate::Result<Response>> + Send;
+}
+
+impl RequestBuilderExt for RequestBuilder {
+    async fn send_with(self, session: &Session) -> crate::Result<Response> {
+        session.send(self, false).await
+    }
+
+    async fn send_idempotent_with(self, session: &Session) -> crate::Result<Response> {
+        session.send(self, true).await
+    }
+}
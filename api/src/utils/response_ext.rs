@@ -1,13 +1,47 @@
 use log::warn;
 use reqwest::Response;
+use reqwest::header::LOCATION;
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+use crate::Error;
+use crate::error::request_id;
+
+pub trait ResponseExt: Sized {
+    /// Turn an unsuccessful response into the respective [`Error`].
+    fn check_status(self) -> impl Future<Output = crate::Result<Self>> + Send;
+
+    /// Decode the JSON response body.
+    fn decode<T>(self) -> impl Future<Output = crate::Result<T>> + Send
+    where
+        T: DeserializeOwned;
 
-pub trait ResponseExt {
     /// Ensure that the response body is empty, logging a warning if not.
-    fn ensure_empty(self) -> impl Future<Output = reqwest::Result<()>> + Send;
+    fn ensure_empty(self) -> impl Future<Output = crate::Result<()>> + Send;
+
+    /// Extract the ID of a created resource from the response's `Location` header.
+    fn created_id(&self) -> Option<Uuid>;
 }
 
 impl ResponseExt for Response {
-    async fn ensure_empty(self) -> reqwest::Result<()> {
+    async fn check_status(self) -> crate::Result<Self> {
+        if self.status().is_success() {
+            Ok(self)
+        } else {
+            Err(Error::from_response(self).await)
+        }
+    }
+
+    async fn decode<T>(self) -> crate::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let request_id = request_id(&self);
+        let body = self.bytes().await?;
+        serde_json::from_slice(&body).map_err(|error| Error::Decode { error, request_id })
+    }
+
+    async fn ensure_empty(self) -> crate::Result<()> {
         let bytes = self.bytes().await?;
 
         if !bytes.is_empty() {
@@ -16,4 +50,12 @@ impl ResponseExt for Response {
 
         Ok(())
     }
+
+    fn created_id(&self) -> Option<Uuid> {
+        self.headers()
+            .get(LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .and_then(|location| location.rsplit('/').next())
+            .and_then(|id| id.parse().ok())
+    }
 }
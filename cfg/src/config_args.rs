@@ -2,9 +2,9 @@ use std::path::Path;
 use std::process::ExitCode;
 
 use log::error;
-use mender_api::{Client, Login, PemCertificate, Session};
+use mender_api::Session;
 
-use crate::ConfigFile;
+use crate::ResolvedConfig;
 
 /// Trait for accessing configuration arguments.
 pub trait ConfigArgs {
@@ -24,78 +24,40 @@ pub trait ConfigArgs {
     #[must_use]
     fn password(&self) -> Option<&str>;
 
-    /// Whether to skip TLS verification.
+    /// Path to the config file, replacing the search of the default paths.
     #[must_use]
-    fn insecure(&self) -> bool;
+    fn config(&self) -> Option<&Path>;
 
-    /// Load configuration from the default path (`$HOME/mender-api.toml`).
+    /// Name of the profile to select from the configuration file.
+    #[must_use]
+    fn profile(&self) -> Option<&str>;
+
+    /// Personal access token to use instead of logging in.
+    #[must_use]
+    fn token(&self) -> Option<&str>;
+
+    /// One-time code for two-factor authentication.
+    #[must_use]
+    fn totp(&self) -> Option<&str>;
+
+    /// Whether to skip TLS verification, if given.
+    #[must_use]
+    fn insecure(&self) -> Option<bool>;
+
+    /// Log in to the Mender server.
+    ///
+    /// The settings are resolved from these arguments, the environment and the config files
+    /// as described in [`ResolvedConfig::resolve`].
     ///
     /// # Errors
     ///
-    /// Returns an error if the home directory could not be determined, or if the file could not be read or parsed.
+    /// Returns an error if the configuration is incomplete or invalid, or if the login fails.
     #[expect(async_fn_in_trait)]
     async fn login(&self) -> Result<Session, ExitCode> {
-        let config_file = match ConfigFile::load() {
-            Ok(config) => config,
-            Err(error) => {
-                error!("Failed to load default config file: {error}");
-                return Err(ExitCode::FAILURE);
-            }
-        };
-
-        let certificate = match self
-            .certificate()
-            .or_else(|| config_file.as_ref().and_then(ConfigFile::certificate))
-            .load()
-        {
-            Ok(cert) => cert,
-            Err(code) => return Err(code),
-        };
-
-        let Some(url) = self
-            .url()
-            .or_else(|| config_file.as_ref().and_then(ConfigFile::url))
-        else {
-            error!("Mender server URL not provided");
-            return Err(ExitCode::FAILURE);
-        };
-
-        let Ok(url) = url
-            .parse()
-            .inspect_err(|error| error!("Invalid URL: {error}"))
-        else {
-            return Err(ExitCode::FAILURE);
-        };
-
-        let insecure = config_file
-            .as_ref()
-            .map_or_else(|| self.insecure(), ConfigFile::insecure);
-
-        let Ok(client) = Client::new(url, certificate, insecure)
-            .inspect_err(|error| error!("Failed to create client: {error}"))
-        else {
-            return Err(ExitCode::FAILURE);
-        };
-
-        let Some(username) = self
-            .username()
-            .or_else(|| config_file.as_ref().and_then(ConfigFile::username))
-        else {
-            error!("Username not provided");
-            return Err(ExitCode::FAILURE);
-        };
-
-        let Some(password) = self
-            .password()
-            .or_else(|| config_file.as_ref().and_then(ConfigFile::password))
-        else {
-            error!("Password not provided");
-            return Err(ExitCode::FAILURE);
-        };
-
-        client.login(username, password).await.map_err(|error| {
-            error!("Login failed: {error}");
+        let config = ResolvedConfig::resolve(self).map_err(|error| {
+            error!("{error}");
             ExitCode::FAILURE
-        })
+        })?;
+        config.login(self.totp()).await
     }
 }
@@ -1,66 +1,237 @@
 //! Configuration file handling for Mender API client.
 
-use std::env::home_dir;
+use std::collections::BTreeMap;
+use std::env::{home_dir, var_os};
 use std::fs::read_to_string;
+use std::io;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
-use log::warn;
+use log::{debug, warn};
 use serde::Deserialize;
 
-use crate::config_args::ConfigArgs;
+pub use self::settings::Settings;
+
+mod settings;
 
 const DEFAULT_FILE_NAME: &str = "mender-api.toml";
+const XDG_DIRECTORY_NAME: &str = "mender-api";
+const XDG_FILE_NAME: &str = "config.toml";
+const SYSTEM_FILE_PATH: &str = "/etc/mender-api.toml";
 
 /// Configuration file for Mender API client.
+///
+/// Settings may be grouped into named profiles in `[profiles.<name>]` sections.
+/// The settings of the selected profile take precedence over the top-level ones.
+/// A profile that changes `url` or `username` does not inherit the top-level `token` or password,
+/// since those belong to another server or account.
+///
+/// ```toml
+/// username = "admin@example.com"
+/// default_profile = "staging"
+///
+/// [profiles.staging]
+/// url = "https://staging.mender.example.com"
+///
+/// [profiles.production]
+/// url = "https://mender.example.com"
+/// certificate = "/etc/ssl/mender.pem"
+/// password_command = "pass show mender/production"
+///
+/// [profiles.on-prem]
+/// url = "https://mender.corp.example.com"
+/// certificates = ["/etc/ssl/corp-root.pem", "/etc/ssl/corp-intermediate.pem"]
+/// client_certificate = "/etc/ssl/client.pem"
+/// client_key = "/etc/ssl/client.key"
+/// proxy = "http://proxy.corp.example.com:3128"
+/// connect_timeout = 10
+/// timeout = 300
+/// user_agent = "fleet-automation/1.0"
+/// headers = { X-Tenant = "acme" }
+/// ```
+///
+/// Timeouts are given in seconds.
 #[derive(Clone, Debug, Deserialize)]
 pub struct ConfigFile {
-    url: Option<String>,
-    certificate: Option<PathBuf>,
-    username: Option<String>,
-    password: Option<String>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(flatten)]
+    settings: Settings,
+    default_profile: Option<String>,
     #[serde(default)]
-    insecure: bool,
+    profiles: BTreeMap<String, Settings>,
 }
 
 impl ConfigFile {
-    /// Load configuration from the default path (`$HOME/mender-api.toml`).
+    /// Load the user's configuration from the first existing file of the default paths:
+    ///
+    /// 1. `$XDG_CONFIG_HOME/mender-api/config.toml`, defaulting to `$HOME/.config`,
+    /// 2. `$HOME/mender-api.toml`.
     ///
     /// # Errors
     ///
-    /// Returns an error if the home directory could not be determined, or if the file could not be read or parsed.
+    /// Returns an error if an existing file could not be read or parsed.
     pub fn load() -> Result<Option<Self>, Box<dyn std::error::Error>> {
-        let Some(config) = read_to_string(
-            home_dir()
-                .ok_or("Could not determine home directory")?
-                .join(DEFAULT_FILE_NAME),
-        )
-        .ok() else {
-            warn!("No config file found at default path, continuing without it.");
-            return Ok(None);
-        };
+        for path in user_paths() {
+            if let Some(config) = load_if_exists(&path)? {
+                return Ok(Some(config));
+            }
+        }
 
-        Ok(Some(toml::from_str(&config)?))
+        debug!("No user config file found at default paths, continuing without it.");
+        Ok(None)
+    }
+
+    /// Load the system-wide configuration from `/etc/mender-api.toml`, if it exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists, but could not be read or parsed.
+    pub fn load_system() -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        load_if_exists(Path::new(SYSTEM_FILE_PATH))
+    }
+
+    /// Load configuration from the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist or could not be read or parsed.
+    pub fn load_from<P>(path: P) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let config = read_to_string(path)
+            .map_err(|error| format!("Failed to read config file {}: {error}", path.display()))?;
+        parse(path, &config)
+    }
+
+    /// Returns the path the configuration was loaded from, if any.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
     }
-}
 
-impl ConfigArgs for ConfigFile {
-    fn url(&self) -> Option<&str> {
-        self.url.as_deref()
+    /// Returns the name of the profile to use if none is selected explicitly.
+    #[must_use]
+    pub fn default_profile(&self) -> Option<&str> {
+        self.default_profile.as_deref()
     }
 
-    fn certificate(&self) -> Option<&Path> {
-        self.certificate.as_deref()
+    /// Returns the names of the available profiles.
+    pub fn profiles(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
     }
 
-    fn username(&self) -> Option<&str> {
-        self.username.as_deref()
+    /// Returns the top-level settings.
+    pub(crate) const fn settings(&self) -> &Settings {
+        &self.settings
     }
 
-    fn password(&self) -> Option<&str> {
-        self.password.as_deref()
+    /// Returns the settings of the profile with the given name, if it exists.
+    pub(crate) fn profile_settings(&self, name: &str) -> Option<&Settings> {
+        self.profiles.get(name)
     }
+}
+
+/// Return the default paths of the user's config file, in order of precedence.
+fn user_paths() -> Vec<PathBuf> {
+    let home = home_dir();
+    let xdg_config_home = var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    xdg_config_home
+        .map(|config| config.join(XDG_DIRECTORY_NAME).join(XDG_FILE_NAME))
+        .into_iter()
+        .chain(home.map(|home| home.join(DEFAULT_FILE_NAME)))
+        .collect()
+}
 
-    fn insecure(&self) -> bool {
-        self.insecure
+/// Load the configuration from the given path, if the file exists.
+fn load_if_exists(path: &Path) -> Result<Option<ConfigFile>, Box<dyn std::error::Error>> {
+    match read_to_string(path) {
+        Ok(config) => parse(path, &config).map(Some),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(format!("Failed to read config file {}: {error}", path.display()).into()),
     }
 }
+
+/// Parse the configuration read from the given path.
+pub fn parse(path: &Path, config: &str) -> Result<ConfigFile, Box<dyn std::error::Error>> {
+    warn_if_accessible(path);
+    toml::from_str(config)
+        .map(|config| ConfigFile {
+            path: Some(path.to_path_buf()),
+            ..config
+        })
+        .map_err(|error| format!("Invalid config file {}: {error}", path.display()).into())
+}
+
+/// Read the password from the given file.
+pub fn read_password_file(path: &Path) -> io::Result<String> {
+    read_to_string(path).map(trim_line_break).map_err(|error| {
+        io::Error::new(
+            error.kind(),
+            format!("Failed to read password file {}: {error}", path.display()),
+        )
+    })
+}
+
+/// Run the password command through the shell and return its output.
+pub fn run_password_command(command: &str) -> io::Result<String> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let output = Command::new(shell)
+        .args([flag, command])
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "Password command failed with {}",
+            output.status
+        )));
+    }
+
+    String::from_utf8(output.stdout)
+        .map(trim_line_break)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Password is not valid UTF-8"))
+}
+
+/// Remove a single trailing line break.
+fn trim_line_break(mut text: String) -> String {
+    if text.ends_with('\n') {
+        text.pop();
+
+        if text.ends_with('\r') {
+            text.pop();
+        }
+    }
+
+    text
+}
+
+/// Warn if the file is readable by group or others, since it may contain secrets.
+#[cfg(unix)]
+fn warn_if_accessible(path: &Path) {
+    if let Ok(metadata) = path.metadata()
+        && metadata.permissions().mode() & 0o044 != 0
+    {
+        warn!(
+            "Config file {} is readable by group or others. Consider restricting its permissions with `chmod 600`.",
+            path.display()
+        );
+    }
+}
+
+/// Warn if the file is readable by group or others, since it may contain secrets.
+#[cfg(not(unix))]
+const fn warn_if_accessible(_path: &Path) {}
@@ -0,0 +1,25 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// Settings of a configuration file, either at its top level or within a named profile.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Settings {
+    pub url: Option<String>,
+    pub certificate: Option<PathBuf>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub password_file: Option<PathBuf>,
+    pub password_command: Option<String>,
+    pub token: Option<String>,
+    pub insecure: Option<bool>,
+    pub certificates: Option<Vec<PathBuf>>,
+    pub client_certificate: Option<PathBuf>,
+    pub client_key: Option<PathBuf>,
+    pub proxy: Option<String>,
+    pub connect_timeout: Option<u64>,
+    pub timeout: Option<u64>,
+    pub user_agent: Option<String>,
+    pub headers: Option<BTreeMap<String, String>>,
+}
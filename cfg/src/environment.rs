@@ -0,0 +1,72 @@
+//! Configuration from environment variables.
+
+use std::env::var;
+
+const URL: &str = "MENDER_URL";
+const USERNAME: &str = "MENDER_USERNAME";
+const PASSWORD: &str = "MENDER_PASSWORD";
+const TOKEN: &str = "MENDER_TOKEN";
+
+/// Configuration from the `MENDER_URL`, `MENDER_USERNAME`, `MENDER_PASSWORD`
+/// and `MENDER_TOKEN` environment variables.
+#[derive(Clone, Debug, Default)]
+pub struct Environment {
+    url: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    token: Option<String>,
+}
+
+impl Environment {
+    /// Read the configuration from the environment.
+    ///
+    /// Variables that are unset, empty or not valid Unicode are ignored.
+    #[must_use]
+    pub fn load() -> Self {
+        Self::new(read(URL), read(USERNAME), read(PASSWORD), read(TOKEN))
+    }
+
+    /// Create the configuration from the given values.
+    pub(crate) const fn new(
+        url: Option<String>,
+        username: Option<String>,
+        password: Option<String>,
+        token: Option<String>,
+    ) -> Self {
+        Self {
+            url,
+            username,
+            password,
+            token,
+        }
+    }
+
+    /// Returns the Mender server URL from `MENDER_URL`.
+    #[must_use]
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// Returns the username from `MENDER_USERNAME`.
+    #[must_use]
+    pub fn username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
+
+    /// Returns the password from `MENDER_PASSWORD`.
+    #[must_use]
+    pub fn password(&self) -> Option<&str> {
+        self.password.as_deref()
+    }
+
+    /// Returns the personal access token from `MENDER_TOKEN`.
+    #[must_use]
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+}
+
+/// Read a non-empty environment variable.
+fn read(name: &str) -> Option<String> {
+    var(name).ok().filter(|value| !value.is_empty())
+}
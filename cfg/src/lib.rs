@@ -2,6 +2,12 @@
 
 pub use self::config_args::ConfigArgs;
 pub use self::config_file::ConfigFile;
+pub use self::environment::Environment;
+pub use self::resolved_config::{Password, ResolvedConfig, Setting, Source};
+pub use self::token_cache::TokenCache;
 
 mod config_args;
 mod config_file;
+mod environment;
+mod resolved_config;
+mod token_cache;
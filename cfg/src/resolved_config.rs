@@ -0,0 +1,940 @@
+//! Configuration resolved from all sources.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs::read;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use log::error;
+use mender_api::{Client, HeaderName, HeaderValue, Identity, PemCertificate, Proxy, Session};
+
+use self::login::login_cached;
+pub use self::password::Password;
+pub use self::setting::{Setting, Source};
+use crate::config_file::Settings;
+use crate::{ConfigArgs, ConfigFile, Environment};
+
+mod login;
+mod password;
+mod setting;
+
+/// Configuration resolved from the defaults, the config files, the environment and arguments.
+#[derive(Clone, Debug)]
+pub struct ResolvedConfig {
+    profile: Option<Setting<String>>,
+    url: Option<Setting<String>>,
+    certificate: Option<Setting<PathBuf>>,
+    username: Option<Setting<String>>,
+    password: Option<Setting<Password>>,
+    token: Option<Setting<String>>,
+    insecure: Setting<bool>,
+    certificates: Option<Setting<Vec<PathBuf>>>,
+    client_certificate: Option<Setting<PathBuf>>,
+    client_key: Option<Setting<PathBuf>>,
+    proxy: Option<Setting<String>>,
+    connect_timeout: Option<Setting<Duration>>,
+    timeout: Option<Setting<Duration>>,
+    user_agent: Option<Setting<String>>,
+    headers: Option<Setting<BTreeMap<String, String>>>,
+}
+
+impl ResolvedConfig {
+    /// Resolve the configuration from the following layers, each overriding the previous ones:
+    ///
+    /// 1. the defaults,
+    /// 2. the system config file (see [`ConfigFile::load_system`]),
+    /// 3. the user config file given by [`ConfigArgs::config`]
+    ///    or found at the default paths (see [`ConfigFile::load`]),
+    /// 4. the `MENDER_URL`, `MENDER_USERNAME`, `MENDER_PASSWORD` and `MENDER_TOKEN`
+    ///    environment variables,
+    /// 5. the given arguments.
+    ///
+    /// Within a config file, the settings of the selected profile override the top-level ones,
+    /// and the password is taken from `password`, `password_file` or `password_command`,
+    /// in this order.
+    ///
+    /// A layer, including a profile, that changes the URL or username discards the token and
+    /// password of the previous layers, unless it sets them itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a config file could not be loaded or the selected profile does not exist.
+    pub fn resolve<A>(args: &A) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        A: ConfigArgs + ?Sized,
+    {
+        let environment = Environment::load();
+        let user_file = match args.config() {
+            Some(path) => Some(ConfigFile::load_from(path)?),
+            None => ConfigFile::load()?,
+        };
+        let files: Vec<_> = ConfigFile::load_system()?
+            .into_iter()
+            .chain(user_file)
+            .collect();
+        Self::from_layers(args, &files, &environment)
+    }
+
+    /// Resolve the configuration from the loaded config files, the environment and arguments.
+    fn from_layers<A>(
+        args: &A,
+        files: &[ConfigFile],
+        environment: &Environment,
+    ) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        A: ConfigArgs + ?Sized,
+    {
+        let profile = args
+            .profile()
+            .map(|name| Setting::new(name.to_owned(), Source::Arguments))
+            .or_else(|| {
+                files.iter().rev().find_map(|file| {
+                    file.default_profile()
+                        .map(|name| Setting::new(name.to_owned(), file_source(file, None)))
+                })
+            });
+
+        let mut config = Self {
+            profile,
+            ..Self::default()
+        };
+        let profile = config
+            .profile
+            .as_ref()
+            .map(|profile| profile.value().clone());
+
+        if let Some(profile) = &profile
+            && !files
+                .iter()
+                .any(|file| file.profile_settings(profile).is_some())
+        {
+            return Err(format!("No such profile: {profile}").into());
+        }
+
+        for file in files {
+            config.apply_settings(file.settings(), &file_source(file, None));
+
+            if let Some(profile) = &profile
+                && let Some(settings) = file.profile_settings(profile)
+            {
+                config.apply_settings(settings, &file_source(file, Some(profile)));
+            }
+        }
+
+        config.apply_environment(environment);
+        config.apply_args(args);
+        Ok(config)
+    }
+
+    /// Returns the selected profile.
+    #[must_use]
+    pub const fn profile(&self) -> Option<&Setting<String>> {
+        self.profile.as_ref()
+    }
+
+    /// Returns the Mender server URL.
+    #[must_use]
+    pub const fn url(&self) -> Option<&Setting<String>> {
+        self.url.as_ref()
+    }
+
+    /// Returns the path to the certificate.
+    #[must_use]
+    pub const fn certificate(&self) -> Option<&Setting<PathBuf>> {
+        self.certificate.as_ref()
+    }
+
+    /// Returns the username.
+    #[must_use]
+    pub const fn username(&self) -> Option<&Setting<String>> {
+        self.username.as_ref()
+    }
+
+    /// Returns the source of the password.
+    #[must_use]
+    pub const fn password(&self) -> Option<&Setting<Password>> {
+        self.password.as_ref()
+    }
+
+    /// Returns the personal access token.
+    #[must_use]
+    pub const fn token(&self) -> Option<&Setting<String>> {
+        self.token.as_ref()
+    }
+
+    /// Returns whether to accept invalid certificates.
+    #[must_use]
+    pub const fn insecure(&self) -> &Setting<bool> {
+        &self.insecure
+    }
+
+    /// Returns the paths to additional root certificates.
+    #[must_use]
+    pub const fn certificates(&self) -> Option<&Setting<Vec<PathBuf>>> {
+        self.certificates.as_ref()
+    }
+
+    /// Returns the path to the client certificate for mutual TLS.
+    #[must_use]
+    pub const fn client_certificate(&self) -> Option<&Setting<PathBuf>> {
+        self.client_certificate.as_ref()
+    }
+
+    /// Returns the path to the private key of the client certificate.
+    #[must_use]
+    pub const fn client_key(&self) -> Option<&Setting<PathBuf>> {
+        self.client_key.as_ref()
+    }
+
+    /// Returns the URL of the HTTP(S) proxy.
+    #[must_use]
+    pub const fn proxy(&self) -> Option<&Setting<String>> {
+        self.proxy.as_ref()
+    }
+
+    /// Returns the timeout for establishing connections.
+    #[must_use]
+    pub const fn connect_timeout(&self) -> Option<&Setting<Duration>> {
+        self.connect_timeout.as_ref()
+    }
+
+    /// Returns the timeout for each request.
+    #[must_use]
+    pub const fn timeout(&self) -> Option<&Setting<Duration>> {
+        self.timeout.as_ref()
+    }
+
+    /// Returns the user agent.
+    #[must_use]
+    pub const fn user_agent(&self) -> Option<&Setting<String>> {
+        self.user_agent.as_ref()
+    }
+
+    /// Returns the headers to send with every request.
+    #[must_use]
+    pub const fn headers(&self) -> Option<&Setting<BTreeMap<String, String>>> {
+        self.headers.as_ref()
+    }
+
+    /// Build a client with the resolved connection settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is missing or invalid, or if any connection setting is invalid.
+    pub fn client(&self) -> Result<Client, ExitCode> {
+        let Some(url) = &self.url else {
+            error!("Mender server URL not provided");
+            return Err(ExitCode::FAILURE);
+        };
+
+        let Ok(url) = url
+            .value()
+            .parse()
+            .inspect_err(|error| error!("Invalid URL: {error}"))
+        else {
+            return Err(ExitCode::FAILURE);
+        };
+
+        let mut builder =
+            Client::builder(url).with_accept_invalid_certificates(*self.insecure.value());
+
+        for path in self
+            .certificate
+            .iter()
+            .map(Setting::value)
+            .chain(self.certificates.iter().flat_map(Setting::value))
+        {
+            builder = builder.with_root_certificates(Some(path).load()?);
+        }
+
+        match (&self.client_certificate, &self.client_key) {
+            (Some(certificate), Some(key)) => {
+                builder = builder.with_identity(
+                    identity(certificate.value(), key.value()).map_err(|error| {
+                        error!("Invalid client identity: {error}");
+                        ExitCode::FAILURE
+                    })?,
+                );
+            }
+            (None, None) => {}
+            _ => {
+                error!("Client certificate and key must be given together");
+                return Err(ExitCode::FAILURE);
+            }
+        }
+
+        if let Some(proxy) = &self.proxy {
+            builder = builder.with_proxy(Proxy::all(proxy.value()).map_err(|error| {
+                error!("Invalid proxy: {error}");
+                ExitCode::FAILURE
+            })?);
+        }
+
+        if let Some(timeout) = &self.connect_timeout {
+            builder = builder.with_connect_timeout(*timeout.value());
+        }
+
+        if let Some(timeout) = &self.timeout {
+            builder = builder.with_timeout(*timeout.value());
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.with_user_agent(user_agent.value());
+        }
+
+        for (name, value) in self.headers.iter().flat_map(Setting::value) {
+            builder = builder.with_header(
+                HeaderName::try_from(name).map_err(|error| {
+                    error!("Invalid header name: {error}");
+                    ExitCode::FAILURE
+                })?,
+                HeaderValue::try_from(value).map_err(|error| {
+                    error!("Invalid header value: {error}");
+                    ExitCode::FAILURE
+                })?,
+            );
+        }
+
+        builder.build().map_err(|error| {
+            error!("Failed to build client: {error}");
+            ExitCode::FAILURE
+        })
+    }
+
+    /// Log in to the Mender server with the resolved configuration.
+    ///
+    /// If a personal access token is configured, the login step is skipped.
+    /// Otherwise, a cached token is reused unless a one-time code for two-factor authentication
+    /// is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is incomplete or invalid, or if the login fails.
+    pub async fn login(&self, totp: Option<&str>) -> Result<Session, ExitCode> {
+        let client = self.client()?;
+
+        if let Some(token) = &self.token {
+            return Ok(Session::from_token(client, token.value().as_str()));
+        }
+
+        let Some(username) = &self.username else {
+            error!("Username not provided");
+            return Err(ExitCode::FAILURE);
+        };
+
+        let Some(password) = &self.password else {
+            error!("Password not provided");
+            return Err(ExitCode::FAILURE);
+        };
+
+        let Ok(password) = password
+            .value()
+            .resolve()
+            .inspect_err(|error| error!("{error}"))
+        else {
+            return Err(ExitCode::FAILURE);
+        };
+
+        login_cached(client, username.value(), &password, totp).await
+    }
+
+    /// Apply the settings of a config file.
+    fn apply_settings(&mut self, settings: &Settings, source: &Source) {
+        set(
+            &mut self.certificate,
+            settings.certificate.as_deref(),
+            source,
+        );
+        self.apply_credentials(
+            settings.url.as_deref(),
+            settings.username.as_deref(),
+            Password::from_settings(settings),
+            settings.token.as_deref(),
+            source,
+        );
+
+        if let Some(insecure) = settings.insecure {
+            self.insecure = Setting::new(insecure, source.clone());
+        }
+
+        set(
+            &mut self.certificates,
+            settings.certificates.as_deref(),
+            source,
+        );
+        set(
+            &mut self.client_certificate,
+            settings.client_certificate.as_deref(),
+            source,
+        );
+        set(&mut self.client_key, settings.client_key.as_deref(), source);
+        set(&mut self.proxy, settings.proxy.as_deref(), source);
+        set(
+            &mut self.connect_timeout,
+            settings.connect_timeout.map(Duration::from_secs).as_ref(),
+            source,
+        );
+        set(
+            &mut self.timeout,
+            settings.timeout.map(Duration::from_secs).as_ref(),
+            source,
+        );
+        set(&mut self.user_agent, settings.user_agent.as_deref(), source);
+        set(&mut self.headers, settings.headers.as_ref(), source);
+    }
+
+    /// Apply the environment variables.
+    fn apply_environment(&mut self, environment: &Environment) {
+        self.apply_credentials(
+            environment.url(),
+            environment.username(),
+            environment
+                .password()
+                .map(|password| Password::Plain(password.to_owned())),
+            environment.token(),
+            &Source::Environment,
+        );
+    }
+
+    /// Apply configuration arguments.
+    fn apply_args<A>(&mut self, args: &A)
+    where
+        A: ConfigArgs + ?Sized,
+    {
+        set(
+            &mut self.certificate,
+            args.certificate(),
+            &Source::Arguments,
+        );
+        self.apply_credentials(
+            args.url(),
+            args.username(),
+            args.password()
+                .map(|password| Password::Plain(password.to_owned())),
+            args.token(),
+            &Source::Arguments,
+        );
+
+        if let Some(insecure) = args.insecure() {
+            self.insecure = Setting::new(insecure, Source::Arguments);
+        }
+    }
+
+    /// Apply the server and credentials of a layer.
+    fn apply_credentials(
+        &mut self,
+        url: Option<&str>,
+        username: Option<&str>,
+        password: Option<Password>,
+        token: Option<&str>,
+        source: &Source,
+    ) {
+        // Credentials of the previous layers belong to another server or account.
+        if changes(self.url.as_ref(), url) || changes(self.username.as_ref(), username) {
+            if token.is_none() {
+                self.token = None;
+            }
+
+            if password.is_none() {
+                self.password = None;
+            }
+        }
+
+        set(&mut self.url, url, source);
+        set(&mut self.username, username, source);
+        set(&mut self.token, token, source);
+
+        if let Some(password) = password {
+            self.password = Some(Setting::new(password, source.clone()));
+        }
+    }
+}
+
+impl Default for ResolvedConfig {
+    fn default() -> Self {
+        Self {
+            profile: None,
+            url: None,
+            certificate: None,
+            username: None,
+            password: None,
+            token: None,
+            insecure: Setting::new(false, Source::Default),
+            certificates: None,
+            client_certificate: None,
+            client_key: None,
+            proxy: None,
+            connect_timeout: None,
+            timeout: None,
+            user_agent: None,
+            headers: None,
+        }
+    }
+}
+
+impl Display for ResolvedConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display_path = |path: &PathBuf| path.display().to_string();
+        let display_timeout = |timeout: &Duration| format!("{}s", timeout.as_secs());
+
+        write_setting(f, "profile", self.profile.as_ref())?;
+        write_setting(f, "url", self.url.as_ref())?;
+        write_setting(
+            f,
+            "certificate",
+            self.certificate
+                .as_ref()
+                .map(|setting| setting.map(display_path))
+                .as_ref(),
+        )?;
+        write_setting(f, "username", self.username.as_ref())?;
+        write_setting(f, "password", self.password.as_ref())?;
+        write_setting(
+            f,
+            "token",
+            self.token
+                .as_ref()
+                .map(|setting| setting.map(|_| "********"))
+                .as_ref(),
+        )?;
+        write_setting(f, "insecure", Some(&self.insecure))?;
+        write_setting(
+            f,
+            "certificates",
+            self.certificates
+                .as_ref()
+                .map(|setting| {
+                    setting.map(|paths| {
+                        paths
+                            .iter()
+                            .map(display_path)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                })
+                .as_ref(),
+        )?;
+        write_setting(
+            f,
+            "client_certificate",
+            self.client_certificate
+                .as_ref()
+                .map(|setting| setting.map(display_path))
+                .as_ref(),
+        )?;
+        write_setting(
+            f,
+            "client_key",
+            self.client_key
+                .as_ref()
+                .map(|setting| setting.map(display_path))
+                .as_ref(),
+        )?;
+        write_setting(f, "proxy", self.proxy.as_ref())?;
+        write_setting(
+            f,
+            "connect_timeout",
+            self.connect_timeout
+                .as_ref()
+                .map(|setting| setting.map(display_timeout))
+                .as_ref(),
+        )?;
+        write_setting(
+            f,
+            "timeout",
+            self.timeout
+                .as_ref()
+                .map(|setting| setting.map(display_timeout))
+                .as_ref(),
+        )?;
+        write_setting(f, "user_agent", self.user_agent.as_ref())?;
+        // Header values may contain secrets, so only show their names.
+        write_setting(
+            f,
+            "headers",
+            self.headers
+                .as_ref()
+                .map(|setting| {
+                    setting.map(|headers| headers.keys().cloned().collect::<Vec<_>>().join(", "))
+                })
+                .as_ref(),
+        )
+    }
+}
+
+/// Override the setting with the value, if any.
+fn set<T>(setting: &mut Option<Setting<T::Owned>>, value: Option<&T>, source: &Source)
+where
+    T: ToOwned + ?Sized,
+{
+    if let Some(value) = value {
+        setting.replace(Setting::new(value.to_owned(), source.clone()));
+    }
+}
+
+/// Return whether the value replaces a different one of the setting.
+fn changes(setting: Option<&Setting<String>>, value: Option<&str>) -> bool {
+    setting
+        .zip(value)
+        .is_some_and(|(setting, value)| setting.value() != value)
+}
+
+/// Return the source of a value from the config file.
+fn file_source(file: &ConfigFile, profile: Option<&str>) -> Source {
+    Source::File {
+        path: file.path().map(Path::to_path_buf).unwrap_or_default(),
+        profile: profile.map(ToOwned::to_owned),
+    }
+}
+
+/// Load a client identity from the PEM files of the certificate and its private key.
+fn identity(certificate: &Path, key: &Path) -> Result<Identity, Box<dyn std::error::Error>> {
+    let mut pem = read(certificate)
+        .map_err(|error| format!("Failed to read {}: {error}", certificate.display()))?;
+    pem.push(b'\n');
+    pem.extend(read(key).map_err(|error| format!("Failed to read {}: {error}", key.display()))?);
+    Ok(Identity::from_pem(&pem)?)
+}
+
+/// Write a line describing the setting and its origin.
+fn write_setting<T>(
+    f: &mut std::fmt::Formatter<'_>,
+    name: &str,
+    setting: Option<&Setting<T>>,
+) -> std::fmt::Result
+where
+    T: Display,
+{
+    match setting {
+        Some(setting) => writeln!(f, "{name}: {} ({})", setting.value(), setting.source()),
+        None => writeln!(f, "{name}: <unset>"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use super::{Password, ResolvedConfig, Source};
+    use crate::config_file::parse;
+    use crate::{ConfigArgs, ConfigFile, Environment};
+
+    const SYSTEM: &str = "/etc/mender-api.toml";
+    const USER: &str = "/home/user/.config/mender-api/config.toml";
+
+    #[derive(Default)]
+    struct Args {
+        url: Option<&'static str>,
+        username: Option<&'static str>,
+        password: Option<&'static str>,
+        profile: Option<&'static str>,
+        token: Option<&'static str>,
+        insecure: Option<bool>,
+    }
+
+    impl ConfigArgs for Args {
+        fn url(&self) -> Option<&str> {
+            self.url
+        }
+
+        fn certificate(&self) -> Option<&Path> {
+            None
+        }
+
+        fn username(&self) -> Option<&str> {
+            self.username
+        }
+
+        fn password(&self) -> Option<&str> {
+            self.password
+        }
+
+        fn config(&self) -> Option<&Path> {
+            None
+        }
+
+        fn profile(&self) -> Option<&str> {
+            self.profile
+        }
+
+        fn token(&self) -> Option<&str> {
+            self.token
+        }
+
+        fn totp(&self) -> Option<&str> {
+            None
+        }
+
+        fn insecure(&self) -> Option<bool> {
+            self.insecure
+        }
+    }
+
+    fn file(path: &str, config: &str) -> ConfigFile {
+        parse(Path::new(path), config).expect("valid config file")
+    }
+
+    fn env(url: Option<&str>, username: Option<&str>, password: Option<&str>) -> Environment {
+        Environment::new(
+            url.map(Into::into),
+            username.map(Into::into),
+            password.map(Into::into),
+            None,
+        )
+    }
+
+    fn resolve(args: &Args, files: &[ConfigFile], environment: &Environment) -> ResolvedConfig {
+        ResolvedConfig::from_layers(args, files, environment).expect("valid configuration")
+    }
+
+    fn file_source(path: &str, profile: Option<&str>) -> Source {
+        Source::File {
+            path: PathBuf::from(path),
+            profile: profile.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn layers_override_each_other_in_order() {
+        let files = [
+            file(
+                SYSTEM,
+                r#"
+                url = "https://system.example.com"
+                proxy = "http://proxy.example.com"
+                timeout = 10
+                "#,
+            ),
+            file(
+                USER,
+                r#"
+                url = "https://user.example.com"
+                timeout = 20
+                "#,
+            ),
+        ];
+        let config = resolve(&Args::default(), &files, &Environment::default());
+        assert_eq!(config.url().unwrap().value(), "https://user.example.com");
+        assert_eq!(config.url().unwrap().source(), &file_source(USER, None));
+        assert_eq!(config.timeout().unwrap().value(), &Duration::from_secs(20));
+        assert_eq!(config.proxy().unwrap().source(), &file_source(SYSTEM, None));
+        assert_eq!(config.insecure().source(), &Source::Default);
+
+        let environment = env(Some("https://env.example.com"), None, None);
+        let config = resolve(&Args::default(), &files, &environment);
+        assert_eq!(config.url().unwrap().value(), "https://env.example.com");
+        assert_eq!(config.url().unwrap().source(), &Source::Environment);
+
+        let args = Args {
+            url: Some("https://args.example.com"),
+            ..Args::default()
+        };
+        let config = resolve(&args, &files, &environment);
+        assert_eq!(config.url().unwrap().value(), "https://args.example.com");
+        assert_eq!(config.url().unwrap().source(), &Source::Arguments);
+    }
+
+    #[test]
+    fn insecure_flag_overrides_config_file() {
+        let files = [file(USER, "insecure = false")];
+        let args = Args {
+            insecure: Some(true),
+            ..Args::default()
+        };
+        let config = resolve(&args, &files, &Environment::default());
+        assert!(config.insecure().value());
+        assert_eq!(config.insecure().source(), &Source::Arguments);
+
+        let files = [file(USER, "insecure = true")];
+        let args = Args {
+            insecure: Some(false),
+            ..Args::default()
+        };
+        let config = resolve(&args, &files, &Environment::default());
+        assert!(!config.insecure().value());
+
+        let config = resolve(&Args::default(), &files, &Environment::default());
+        assert!(config.insecure().value());
+        assert_eq!(config.insecure().source(), &file_source(USER, None));
+    }
+
+    #[test]
+    fn user_file_disables_insecure_of_system_file() {
+        let files = [
+            file(SYSTEM, "insecure = true"),
+            file(USER, "insecure = false"),
+        ];
+        let config = resolve(&Args::default(), &files, &Environment::default());
+        assert!(!config.insecure().value());
+    }
+
+    #[test]
+    fn changed_url_discards_credentials() {
+        let files = [file(
+            USER,
+            r#"
+            url = "https://mender.example.com"
+            username = "admin@example.com"
+            password = "secret"
+            token = "token"
+            "#,
+        )];
+        let environment = env(Some("https://other.example.com"), None, None);
+        let config = resolve(&Args::default(), &files, &environment);
+        assert_eq!(config.username().unwrap().value(), "admin@example.com");
+        assert!(config.password().is_none());
+        assert!(config.token().is_none());
+
+        let args = Args {
+            url: Some("https://other.example.com"),
+            password: Some("other"),
+            ..Args::default()
+        };
+        let config = resolve(&args, &files, &Environment::default());
+        assert_eq!(
+            config.password().unwrap().value(),
+            &Password::Plain("other".into())
+        );
+        assert!(config.token().is_none());
+
+        let environment = env(Some("https://mender.example.com"), None, None);
+        let config = resolve(&Args::default(), &files, &environment);
+        assert!(config.password().is_some());
+        assert!(config.token().is_some());
+    }
+
+    #[test]
+    fn changed_username_discards_password() {
+        let files = [file(
+            USER,
+            r#"
+            username = "admin@example.com"
+            password_file = "/run/secrets/mender"
+            "#,
+        )];
+        let environment = env(None, Some("admin@example.com"), None);
+        let config = resolve(&Args::default(), &files, &environment);
+        assert_eq!(
+            config.password().unwrap().value(),
+            &Password::File("/run/secrets/mender".into())
+        );
+
+        let args = Args {
+            username: Some("other@example.com"),
+            ..Args::default()
+        };
+        let config = resolve(&args, &files, &environment);
+        assert_eq!(config.username().unwrap().source(), &Source::Arguments);
+        assert!(config.password().is_none());
+    }
+
+    #[test]
+    fn profile_with_other_url_discards_credentials() {
+        let files = [file(
+            USER,
+            r#"
+            url = "https://mender.example.com"
+            password = "secret"
+            default_profile = "staging"
+
+            [profiles.staging]
+            url = "https://staging.example.com"
+
+            [profiles.production]
+            url = "https://mender.example.com"
+            certificate = "/etc/ssl/mender.pem"
+            "#,
+        )];
+        let config = resolve(&Args::default(), &files, &Environment::default());
+        assert_eq!(config.profile().unwrap().source(), &file_source(USER, None));
+        assert_eq!(
+            config.url().unwrap().source(),
+            &file_source(USER, Some("staging"))
+        );
+        assert!(config.password().is_none());
+
+        let args = Args {
+            profile: Some("production"),
+            ..Args::default()
+        };
+        let config = resolve(&args, &files, &Environment::default());
+        assert_eq!(
+            config.password().unwrap().source(),
+            &file_source(USER, None)
+        );
+
+        let args = Args {
+            profile: Some("development"),
+            ..Args::default()
+        };
+        assert!(ResolvedConfig::from_layers(&args, &files, &Environment::default()).is_err());
+    }
+
+    #[test]
+    fn password_sources_take_precedence_in_order() {
+        let files = [file(
+            USER,
+            r#"
+            password = "secret"
+            password_file = "/run/secrets/mender"
+            password_command = "pass show mender"
+            "#,
+        )];
+        let config = resolve(&Args::default(), &files, &Environment::default());
+        assert_eq!(
+            config.password().unwrap().value(),
+            &Password::Plain("secret".into())
+        );
+
+        let files = [file(
+            USER,
+            r#"
+            password_file = "/run/secrets/mender"
+            password_command = "pass show mender"
+            "#,
+        )];
+        let config = resolve(&Args::default(), &files, &Environment::default());
+        assert_eq!(
+            config.password().unwrap().value(),
+            &Password::File("/run/secrets/mender".into())
+        );
+
+        let environment = env(None, None, Some("environment"));
+        let config = resolve(&Args::default(), &files, &environment);
+        assert_eq!(
+            config.password().unwrap().value(),
+            &Password::Plain("environment".into())
+        );
+        assert_eq!(config.password().unwrap().source(), &Source::Environment);
+    }
+
+    #[test]
+    fn display_masks_secrets() {
+        let files = [file(
+            USER,
+            r#"
+            url = "https://mender.example.com"
+            username = "admin@example.com"
+            password = "hunter2"
+            token = "eyJhbGciOi"
+            headers = { X-Tenant = "acme" }
+            "#,
+        )];
+        let config = resolve(&Args::default(), &files, &Environment::default());
+        let display = config.to_string();
+        assert!(display.contains(&format!(
+            "url: https://mender.example.com (config file {USER})"
+        )));
+        assert!(display.contains(&format!("password: ******** (config file {USER})")));
+        assert!(display.contains(&format!("token: ******** (config file {USER})")));
+        assert!(display.contains(&format!("headers: X-Tenant (config file {USER})")));
+        assert!(display.contains("insecure: false (default)"));
+        assert!(display.contains("proxy: <unset>"));
+        assert!(!display.contains("hunter2"));
+        assert!(!display.contains("eyJhbGciOi"));
+        assert!(!display.contains("acme"));
+    }
+}
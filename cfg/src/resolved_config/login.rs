@@ -0,0 +1,85 @@
+use std::io::{self, IsTerminal, Write, stderr, stdin};
+use std::process::ExitCode;
+
+use log::{error, warn};
+use mender_api::{Client, Error, Login, Session};
+
+use crate::TokenCache;
+
+/// Log in with the given credentials, reusing a cached token if possible.
+pub async fn login_cached(
+    client: Client,
+    username: &str,
+    password: &str,
+    totp: Option<&str>,
+) -> Result<Session, ExitCode> {
+    let mut token_cache = TokenCache::load()
+        .inspect_err(|error| warn!("Failed to load token cache: {error}"))
+        .unwrap_or_default();
+
+    // A one-time code is only given to replace a token that is missing or was rejected.
+    if totp.is_none()
+        && let Some(session) = token_cache.session(&client, username)
+    {
+        return Ok(session.with_credentials(username, password));
+    }
+
+    let url = client.base_url().to_string();
+    let session = login(client, username, password, totp)
+        .await
+        .map_err(|error| {
+            error!("Login failed: {error}");
+
+            if token_cache.remove(&url, username)
+                && let Err(error) = token_cache.save()
+            {
+                warn!("Failed to save token cache: {error}");
+            }
+
+            if matches!(error, Error::SecondFactorRequired(_)) {
+                error!("Provide the one-time code of your authenticator app.");
+            }
+
+            ExitCode::FAILURE
+        })?;
+
+    if token_cache.insert(&session, username)
+        && let Err(error) = token_cache.save()
+    {
+        warn!("Failed to save token cache: {error}");
+    }
+
+    Ok(session)
+}
+
+/// Log in with the given credentials.
+///
+/// If the server requires a second factor that was not given and we run interactively,
+/// prompt for it.
+async fn login(
+    client: Client,
+    username: &str,
+    password: &str,
+    totp: Option<&str>,
+) -> mender_api::Result<Session> {
+    if let Some(totp) = totp {
+        return client.login_with_totp(username, password, totp).await;
+    }
+
+    match client.clone().login(username, password).await {
+        Err(Error::SecondFactorRequired(error)) if stdin().is_terminal() => match prompt_totp() {
+            Ok(totp) => client.login_with_totp(username, password, totp).await,
+            Err(_) => Err(Error::SecondFactorRequired(error)),
+        },
+        result => result,
+    }
+}
+
+/// Prompt for the one-time code of two-factor authentication.
+fn prompt_totp() -> io::Result<String> {
+    eprint!("Two-factor authentication code: ");
+    stderr().flush()?;
+    let mut totp = String::new();
+    stdin().read_line(&mut totp)?;
+    Ok(totp.trim().to_owned())
+}
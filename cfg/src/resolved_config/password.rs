@@ -0,0 +1,63 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::io;
+use std::path::PathBuf;
+
+use crate::config_file::{Settings, read_password_file, run_password_command};
+
+/// Source of the password.
+#[derive(Clone, Eq, PartialEq)]
+pub enum Password {
+    /// The password itself.
+    Plain(String),
+    /// A file containing the password.
+    File(PathBuf),
+    /// A command printing the password.
+    Command(String),
+}
+
+impl Password {
+    /// Returns the password source of the settings, if any, preferring a plain password
+    /// over a password file over a password command.
+    pub(crate) fn from_settings(settings: &Settings) -> Option<Self> {
+        settings
+            .password
+            .clone()
+            .map(Self::Plain)
+            .or_else(|| settings.password_file.clone().map(Self::File))
+            .or_else(|| settings.password_command.clone().map(Self::Command))
+    }
+
+    /// Read the password from its source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the password file could not be read, or the password command failed.
+    pub fn resolve(&self) -> io::Result<Cow<'_, str>> {
+        match self {
+            Self::Plain(password) => Ok(Cow::Borrowed(password)),
+            Self::File(path) => read_password_file(path).map(Cow::Owned),
+            Self::Command(command) => run_password_command(command).map(Cow::Owned),
+        }
+    }
+}
+
+impl std::fmt::Debug for Password {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Plain(_) => f.debug_tuple("Plain").field(&"********").finish(),
+            Self::File(path) => f.debug_tuple("File").field(path).finish(),
+            Self::Command(command) => f.debug_tuple("Command").field(command).finish(),
+        }
+    }
+}
+
+impl Display for Password {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Plain(_) => write!(f, "********"),
+            Self::File(path) => write!(f, "read from {}", path.display()),
+            Self::Command(command) => write!(f, "output of `{command}`"),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+
+/// A configuration value along with its origin.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Setting<T> {
+    value: T,
+    source: Source,
+}
+
+impl<T> Setting<T> {
+    /// Creates a new `Setting` instance.
+    #[must_use]
+    pub const fn new(value: T, source: Source) -> Self {
+        Self { value, source }
+    }
+
+    /// Returns the value.
+    #[must_use]
+    pub const fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Map the value, keeping its origin.
+    #[must_use]
+    pub fn map<F, U>(&self, f: F) -> Setting<U>
+    where
+        F: FnOnce(&T) -> U,
+    {
+        Setting::new(f(&self.value), self.source.clone())
+    }
+
+    /// Returns the origin of the value.
+    #[must_use]
+    pub const fn source(&self) -> &Source {
+        &self.source
+    }
+}
+
+/// Origin of a configuration value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    /// The built-in default.
+    Default,
+    /// A config file, optionally within a named profile.
+    File {
+        /// Path to the config file.
+        path: PathBuf,
+        /// Name of the profile, if the value was set within one.
+        profile: Option<String>,
+    },
+    /// An environment variable.
+    Environment,
+    /// A command line argument.
+    Arguments,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::File { path, profile } => {
+                write!(f, "config file {}", path.display())?;
+
+                if let Some(profile) = profile {
+                    write!(f, ", profile {profile}")?;
+                }
+
+                Ok(())
+            }
+            Self::Environment => write!(f, "environment"),
+            Self::Arguments => write!(f, "command line"),
+        }
+    }
+}
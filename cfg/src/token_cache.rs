@@ -0,0 +1,141 @@
+//! On-disk cache of session tokens, to avoid logging in on every invocation.
+
+use std::collections::BTreeMap;
+use std::env::{home_dir, var_os};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::PathBuf;
+
+use chrono::{TimeDelta, Utc};
+use log::warn;
+use mender_api::{Client, Session};
+use serde::{Deserialize, Serialize};
+
+const DIRECTORY_NAME: &str = "mender-api";
+const FILE_NAME: &str = "tokens.toml";
+
+/// Minimum remaining validity of a cached token to be reused.
+const MIN_VALIDITY: TimeDelta = TimeDelta::minutes(1);
+
+/// Cache of session tokens, keyed by server URL and username.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct TokenCache {
+    #[serde(default)]
+    tokens: BTreeMap<String, String>,
+}
+
+impl TokenCache {
+    /// Load the token cache from the user's cache directory.
+    ///
+    /// If no cache file exists yet, an empty cache is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory could not be determined, or if the file could not be read or parsed.
+    pub fn load() -> io::Result<Self> {
+        match fs::read_to_string(path()?) {
+            Ok(text) => toml::from_str(&text).map_err(io::Error::other),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Save the token cache to the user's cache directory, readable by the user only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory could not be determined, or if the file could not be written.
+    pub fn save(&self) -> io::Result<()> {
+        let path = path()?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(&path)?;
+        // The file may have been created with broader permissions before.
+        #[cfg(unix)]
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        file.write_all(toml::to_string(self).map_err(io::Error::other)?.as_bytes())
+    }
+
+    /// Create a session from the cached token for the given user, if it is not about to expire.
+    #[must_use]
+    pub fn session(&self, client: &Client, username: &str) -> Option<Session> {
+        let token = self
+            .tokens
+            .get(&key(client.base_url().as_str(), username))?;
+        let session = Session::from_token(client.clone(), token.as_str());
+        session
+            .expires_at()
+            .is_some_and(|expires_at| expires_at - MIN_VALIDITY > Utc::now())
+            .then_some(session)
+    }
+
+    /// Store the current token of the session for the given user.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the cached token changed.
+    pub fn insert(&mut self, session: &Session, username: &str) -> bool {
+        let token = session.bearer_token();
+        self.tokens
+            .insert(key(session.base_url().as_str(), username), token.clone())
+            .is_none_or(|previous| previous != token)
+    }
+
+    /// Remove the cached token of the given user on the server at the given URL.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if a token was removed.
+    pub fn remove(&mut self, url: &str, username: &str) -> bool {
+        self.tokens.remove(&key(url, username)).is_some()
+    }
+
+    /// Update the cache with the current token of a session that keeps its credentials,
+    /// e.g. after it logged in again because the cached token was rejected.
+    ///
+    /// If the server rejected the token and the session could not log in again,
+    /// the token is dropped from the cache instead.
+    pub fn update(session: &Session) {
+        let Some(username) = session.username() else {
+            return;
+        };
+
+        let result = Self::load().and_then(|mut cache| {
+            let changed = if session.token_rejected() {
+                cache.remove(session.base_url().as_str(), username)
+            } else {
+                cache.insert(session, username)
+            };
+
+            if changed { cache.save() } else { Ok(()) }
+        });
+
+        if let Err(error) = result {
+            warn!("Failed to update token cache: {error}");
+        }
+    }
+}
+
+/// Return the key of a token in the cache.
+fn key(url: &str, username: &str) -> String {
+    format!("{username}@{url}")
+}
+
+/// Return the path of the cache file.
+fn path() -> io::Result<PathBuf> {
+    var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| home_dir().map(|home| home.join(".cache")))
+        .map(|cache| cache.join(DIRECTORY_NAME).join(FILE_NAME))
+        .ok_or_else(|| io::Error::other("Could not determine cache directory"))
+}
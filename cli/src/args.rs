@@ -1,24 +1,32 @@
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
-use clap::{Parser, Subcommand};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand};
 use mender_api::{Devices, Session};
-use mender_api_cfg::ConfigArgs;
+use mender_api_cfg::{ConfigArgs, ResolvedConfig, TokenCache};
 use uuid::Uuid;
 
 use self::artifact_action::ArtifactAction;
 use self::deployments_action::DeploymentAction;
 use self::device_action::DeviceAction;
+use self::device_auth_action::DeviceAuthAction;
 use self::device_proxy_action::DeviceProxyAction;
+use self::filter_action::FilterAction;
 use self::group_action::GroupAction;
 use self::release_action::ReleaseAction;
+use self::token_action::TokenAction;
+use crate::util::OrBail;
 
 mod artifact_action;
 mod deployments_action;
 mod device_action;
+mod device_auth_action;
 mod device_proxy_action;
+mod filter_action;
 mod group_action;
 mod release_action;
+mod token_action;
 
 #[derive(Debug, Parser)]
 pub struct Args {
@@ -28,18 +36,64 @@ pub struct Args {
     username: Option<String>,
     #[clap(long, short, help = "Password for Mender server login")]
     password: Option<String>,
-    #[clap(long, short = 'k', help = "Accept invalid certificates (insecure)")]
+    #[clap(long, help = "Path to the config file")]
+    config: Option<PathBuf>,
+    #[clap(long, short = 'P', help = "Name of the profile in the config file")]
+    profile: Option<String>,
+    #[clap(long, help = "One-time code for two-factor authentication")]
+    totp: Option<String>,
+    #[clap(
+        long,
+        short,
+        help = "Personal access token to use instead of username and password"
+    )]
+    token: Option<String>,
+    #[clap(
+        long,
+        short = 'k',
+        overrides_with = "no_insecure",
+        help = "Accept invalid certificates (insecure)"
+    )]
     insecure: bool,
+    #[clap(
+        long,
+        overrides_with = "insecure",
+        help = "Verify certificates, even if the config file sets insecure"
+    )]
+    no_insecure: bool,
     #[clap(long, short, help = "Path to the certificate file (optional)")]
     certificate: Option<PathBuf>,
+    #[clap(
+        long,
+        help = "Show the resolved configuration and where each value came from, then exit"
+    )]
+    explain_config: bool,
     #[clap(subcommand)]
-    endpoint: Endpoint,
+    endpoint: Option<Endpoint>,
 }
 
 impl Args {
-    pub async fn run(self) -> Result<(), ExitCode> {
+    pub async fn run(mut self) -> Result<(), ExitCode> {
+        if self.explain_config {
+            let config = ResolvedConfig::resolve(&self).or_bail()?;
+            print!("{config}");
+            return Ok(());
+        }
+
+        let Some(endpoint) = self.endpoint.take() else {
+            Self::command()
+                .error(ErrorKind::MissingSubcommand, "A command is required")
+                .exit();
+        };
+
+        if let Some(result) = endpoint.run_offline() {
+            return result;
+        }
+
         let session = self.login().await?;
-        self.endpoint.run(&session).await
+        let result = endpoint.run(&session).await;
+        TokenCache::update(&session);
+        result
     }
 }
 
@@ -60,8 +114,30 @@ impl ConfigArgs for Args {
         self.password.as_deref()
     }
 
-    fn insecure(&self) -> bool {
-        self.insecure
+    fn config(&self) -> Option<&Path> {
+        self.config.as_deref()
+    }
+
+    fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    fn totp(&self) -> Option<&str> {
+        self.totp.as_deref()
+    }
+
+    fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    fn insecure(&self) -> Option<bool> {
+        if self.insecure {
+            Some(true)
+        } else if self.no_insecure {
+            Some(false)
+        } else {
+            None
+        }
     }
 }
 
@@ -79,6 +155,14 @@ pub enum Endpoint {
         #[clap(subcommand)]
         action: DeviceAction,
     },
+    Devauth {
+        #[clap(subcommand)]
+        action: DeviceAuthAction,
+    },
+    Filters {
+        #[clap(subcommand)]
+        action: FilterAction,
+    },
     Groups {
         #[clap(subcommand)]
         action: GroupAction,
@@ -87,6 +171,10 @@ pub enum Endpoint {
         #[clap(subcommand)]
         action: ReleaseAction,
     },
+    Tokens {
+        #[clap(subcommand)]
+        action: TokenAction,
+    },
     #[clap(name = "device")]
     DeviceProxy {
         #[clap(help = "ID of the device to manage")]
@@ -97,13 +185,25 @@ pub enum Endpoint {
 }
 
 impl Endpoint {
+    /// Run the endpoint action if it does not require a session.
+    pub fn run_offline(&self) -> Option<Result<(), ExitCode>> {
+        match self {
+            Self::Artifacts { action } => action.run_offline(),
+            Self::Devices { action } => action.run_offline(),
+            _ => None,
+        }
+    }
+
     pub async fn run(self, session: &Session) -> Result<(), ExitCode> {
         match self {
             Self::Artifacts { action } => action.run(session).await,
             Self::Deployments { action } => action.run(session).await,
             Self::Devices { action } => action.run(session).await,
+            Self::Devauth { action } => action.run(session).await,
+            Self::Filters { action } => action.run(session).await,
             Self::Groups { action } => action.run(session).await,
             Self::Releases { action } => action.run(session).await,
+            Self::Tokens { action } => action.run(session).await,
             Self::DeviceProxy { id, action } => action.run(session.proxy(id)).await,
         }
     }
@@ -1,11 +1,17 @@
+use std::fs::read_to_string;
 use std::num::NonZero;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 use clap::Subcommand;
 use log::error;
-use mender_api::{Artifacts, Session};
+use mender_api::archive::PublicKey;
+use mender_api::dto::DeviceType;
+use mender_api::{ArtifactGeneration, ArtifactUpload, Artifacts, Session, archive};
 use uuid::Uuid;
 
+use crate::util::OrBail;
+
 #[derive(Debug, Subcommand)]
 pub enum ArtifactAction {
     List {
@@ -18,9 +24,63 @@ pub enum ArtifactAction {
         #[clap(index = 1, help = "Delete an artifact by its ID")]
         id: Uuid,
     },
+    Upload {
+        #[clap(index = 1, help = "Path to the artifact file to upload")]
+        file: PathBuf,
+        #[clap(long, short = 'd', help = "Optional description for the artifact")]
+        description: Option<String>,
+    },
+    Generate {
+        #[clap(index = 1, help = "Name of the artifact to generate")]
+        name: String,
+        #[clap(index = 2, help = "Path to the payload file")]
+        file: PathBuf,
+        #[clap(
+            long,
+            short = 't',
+            default_value = "single-file",
+            help = "Update type of the artifact"
+        )]
+        update_type: String,
+        #[clap(long, short = 'D', required = true, help = "Compatible device types")]
+        device_types: Vec<DeviceType>,
+        #[clap(long, short = 'd', help = "Optional description for the artifact")]
+        description: Option<String>,
+        #[clap(long, short = 'a', help = "Update module arguments as a JSON string")]
+        args: Option<String>,
+    },
+    Inspect {
+        #[clap(index = 1, help = "Path to the artifact file to inspect")]
+        file: PathBuf,
+    },
+    Verify {
+        #[clap(index = 1, help = "Path to the artifact file to verify")]
+        file: PathBuf,
+        #[clap(long, short = 'k', help = "Path to the public key in PEM format")]
+        key: PathBuf,
+    },
+    DownloadLink {
+        #[clap(index = 1, help = "ID of the artifact")]
+        id: Uuid,
+    },
+    Download {
+        #[clap(index = 1, help = "ID of the artifact to download")]
+        id: Uuid,
+        #[clap(index = 2, help = "Path to store the artifact at")]
+        path: PathBuf,
+    },
 }
 
 impl ArtifactAction {
+    /// Run the action if it does not require a session.
+    pub fn run_offline(&self) -> Option<Result<(), ExitCode>> {
+        match self {
+            Self::Inspect { file } => Some(inspect(file)),
+            Self::Verify { file, key } => Some(verify(file, key)),
+            _ => None,
+        }
+    }
+
     pub async fn run(self, session: &Session) -> Result<(), ExitCode> {
         match self {
             Self::List { page_size, verbose } => {
@@ -51,8 +111,100 @@ impl ArtifactAction {
                     return Err(ExitCode::FAILURE);
                 }
             },
+            Self::Upload { file, description } => {
+                let mut upload = ArtifactUpload::open(&file).await.or_bail()?;
+
+                if let Some(description) = description {
+                    upload = upload.with_description(description);
+                }
+
+                let size = upload.size().unwrap_or_default();
+                let id = Artifacts::upload(session, upload, move |sent| {
+                    eprint!("\rUploaded {sent} of {size} bytes");
+                })
+                .await;
+                eprintln!();
+
+                match id.or_bail()? {
+                    Some(id) => println!("Artifact {id} uploaded successfully."),
+                    None => println!("Artifact uploaded successfully."),
+                }
+            }
+            Self::Generate {
+                name,
+                file,
+                update_type,
+                device_types,
+                description,
+                args,
+            } => {
+                let mut generation = ArtifactGeneration::open(name, update_type, &file)
+                    .await
+                    .or_bail()?
+                    .with_compatible_device_types(device_types);
+
+                if let Some(description) = description {
+                    generation = generation.with_description(description);
+                }
+
+                if let Some(args) = args {
+                    generation = generation.with_args(args);
+                }
+
+                let size = generation.size().unwrap_or_default();
+                let id = Artifacts::generate(session, generation, move |sent| {
+                    eprint!("\rUploaded {sent} of {size} bytes");
+                })
+                .await;
+                eprintln!();
+
+                match id.or_bail()? {
+                    Some(id) => println!("Artifact {id} is being generated."),
+                    None => println!("Artifact is being generated."),
+                }
+            }
+            Self::Inspect { file } => inspect(&file)?,
+            Self::Verify { file, key } => verify(&file, &key)?,
+            Self::DownloadLink { id } => {
+                let link = Artifacts::download_link(session, id).await.or_bail()?;
+                println!("{} (expires {})", link.uri(), link.expire());
+            }
+            Self::Download { id, path } => {
+                let artifact = Artifacts::get(session, id).await.or_bail()?;
+                Artifacts::download(session, &artifact, &path)
+                    .await
+                    .or_bail()?;
+                println!(
+                    "Artifact {id} downloaded to {} and verified.",
+                    path.display()
+                );
+            }
         }
 
         Ok(())
     }
 }
+
+fn inspect(file: &Path) -> Result<(), ExitCode> {
+    let artifact_file = archive::open(file).or_bail()?;
+    println!("{:#}", artifact_file.artifact());
+
+    for (index, meta_data) in artifact_file.meta_data().iter().enumerate() {
+        if let Some(meta_data) = meta_data {
+            println!("Meta data of payload {index}:");
+
+            for (key, value) in meta_data {
+                println!("\t- {key}: {value}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn verify(file: &Path, key: &Path) -> Result<(), ExitCode> {
+    let key: PublicKey = read_to_string(key).or_bail()?.parse().or_bail()?;
+    archive::open(file).or_bail()?.verify(&key).or_bail()?;
+    println!("Signature of {} is valid.", file.display());
+    Ok(())
+}
@@ -1,10 +1,15 @@
+use std::fs::{File, create_dir_all, write};
+use std::io::BufReader;
 use std::num::NonZero;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
-use clap::Subcommand;
-use log::error;
-use mender_api::dto::DeploymentStatus;
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand};
+use log::{error, info, warn};
+use mender_api::dto::{DeploymentStatus, DeviceDeploymentStatus, NewDeployment, Phase};
 use mender_api::{Deployments, Session};
+use serde_json::{Map, Value};
 use uuid::Uuid;
 
 use crate::util::OrBail;
@@ -27,16 +32,33 @@ pub enum DeploymentAction {
         #[clap(index = 1, help = "List device for a specific deployment")]
         id: Uuid,
     },
-    Add {
-        #[clap(index = 1, help = "Name of the deployment")]
-        name: String,
-        #[clap(index = 2, help = "Artifact name for the deployment")]
-        artifact_name: String,
-        #[clap(long, short = 'D', help = "Devices to deploy")]
-        devices: Vec<Uuid>,
-        #[clap(long, short = 'R', help = "Number of retries for the deployment")]
-        retries: usize,
+    Statistics {
+        #[clap(index = 1, help = "ID of the deployment")]
+        id: Uuid,
+    },
+    DeviceStatuses {
+        #[clap(index = 1, help = "ID of the deployment")]
+        id: Uuid,
+        #[clap(long, short = 's', help = "List only devices with the given status")]
+        status: Vec<DeviceDeploymentStatus>,
+        #[clap(long, short = 'p', help = "Page size for device querying")]
+        page_size: Option<NonZero<usize>>,
+    },
+    Log {
+        #[clap(index = 1, help = "ID of the deployment")]
+        id: Uuid,
+        #[clap(index = 2, help = "ID of the device")]
+        device_id: Uuid,
     },
+    FailedLogs {
+        #[clap(index = 1, help = "ID of the deployment")]
+        id: Uuid,
+        #[clap(index = 2, help = "Directory to store the device logs in")]
+        directory: PathBuf,
+        #[clap(long, short = 'p', help = "Page size for device querying")]
+        page_size: Option<NonZero<usize>>,
+    },
+    Add(AddDeployment),
     Abort {
         #[clap(index = 1, help = "ID of the deployment to abort")]
         id: Uuid,
@@ -51,6 +73,76 @@ pub enum DeploymentAction {
     },
 }
 
+#[derive(Debug, Args)]
+pub struct AddDeployment {
+    #[clap(index = 1, help = "Name of the deployment")]
+    name: String,
+    #[clap(index = 2, help = "Artifact name for the deployment")]
+    artifact_name: String,
+    #[clap(long, short = 'D', help = "Devices to deploy")]
+    devices: Vec<Uuid>,
+    #[clap(
+        long,
+        short = 'f',
+        conflicts_with = "devices",
+        help = "ID of a saved inventory filter whose devices to deploy"
+    )]
+    filter: Option<Uuid>,
+    #[clap(long, short = 'R', help = "Number of retries for the deployment")]
+    retries: usize,
+    #[clap(
+        long = "phase",
+        short = 'P',
+        help = "Deployment phase as [PERCENT][@START_TIME], may be given multiple times"
+    )]
+    phases: Vec<Phase>,
+    #[clap(long, short = 's', help = "Start time of the deployment (RFC 3339)")]
+    start: Option<DateTime<Utc>>,
+    #[clap(
+        long,
+        short = 'm',
+        help = "Maximum number of devices for the deployment"
+    )]
+    max_devices: Option<usize>,
+    #[clap(
+        long,
+        short = 'u',
+        help = "Path to a JSON file with the update control map"
+    )]
+    update_control_map: Option<PathBuf>,
+}
+
+impl AddDeployment {
+    pub async fn run(self, session: &Session) -> Result<(), ExitCode> {
+        let mut deployment = NewDeployment::new(&self.name, &self.artifact_name)
+            .with_devices(&self.devices)
+            .with_retries(self.retries)
+            .with_phases(self.phases);
+
+        if let Some(start) = self.start {
+            deployment = deployment.with_start_time(start);
+        }
+
+        if let Some(max_devices) = self.max_devices {
+            deployment = deployment.with_max_devices(max_devices);
+        }
+
+        if let Some(path) = &self.update_control_map {
+            deployment = deployment.with_update_control_map(read_update_control_map(path)?);
+        }
+
+        match self.filter {
+            Some(filter_id) => {
+                Deployments::create_for_filter_from(session, filter_id, &deployment).await
+            }
+            None => Deployments::create_from(session, &deployment).await,
+        }
+        .or_bail()?;
+
+        Ok(())
+    }
+}
+
 impl DeploymentAction {
     pub async fn run(self, session: &Session) -> Result<(), ExitCode> {
         match self {
@@ -88,15 +180,58 @@ impl DeploymentAction {
                     println!("{device_id}");
                 }
             }
-            Self::Add {
-                name,
-                artifact_name,
-                devices,
-                retries,
+            Self::Statistics { id } => {
+                let statistics = Deployments::statistics(session, id).await.or_bail()?;
+                println!("{statistics:#}");
+            }
+            Self::DeviceStatuses {
+                id,
+                status,
+                page_size,
             } => {
-                Deployments::create(session, name, artifact_name, &devices, retries)
+                for device in Deployments::device_statuses(session, id, page_size)
+                    .await
+                    .or_bail()?
+                    .into_iter()
+                    .filter(|device| status.is_empty() || status.contains(&device.status()))
+                {
+                    println!("{device}");
+                }
+            }
+            Self::Log { id, device_id } => {
+                let log = Deployments::device_log(session, id, device_id)
                     .await
                     .or_bail()?;
+                println!("{log}");
+            }
+            Self::FailedLogs {
+                id,
+                directory,
+                page_size,
+            } => {
+                create_dir_all(&directory).or_bail()?;
+
+                for device in Deployments::device_statuses(session, id, page_size)
+                    .await
+                    .or_bail()?
+                    .into_iter()
+                    .filter(|device| device.status() == DeviceDeploymentStatus::Failure)
+                {
+                    if !device.log_available() {
+                        warn!("No log available for device {}", device.id());
+                        continue;
+                    }
+
+                    let log = Deployments::device_log(session, id, device.id())
+                        .await
+                        .or_bail()?;
+                    let path = directory.join(format!("{}.log", device.id()));
+                    write(&path, log.text()).or_bail()?;
+                    info!("Saved log of device {} to {}", device.id(), path.display());
+                }
+            }
+            Self::Add(add) => {
+                add.run(session).await?;
             }
             Self::Abort { id } => {
                 Deployments::abort(session, id).await.or_bail()?;
@@ -114,3 +249,8 @@ impl DeploymentAction {
         Ok(())
     }
 }
+
+/// Read an update control map from a JSON file.
+fn read_update_control_map(path: &Path) -> Result<Map<String, Value>, ExitCode> {
+    serde_json::from_reader(BufReader::new(File::open(path).or_bail()?)).or_bail()
+}
@@ -1,14 +1,21 @@
 use std::num::NonZero;
+use std::path::PathBuf;
 use std::process::ExitCode;
 
 use clap::Subcommand;
 use log::error;
 use macaddr::MacAddr6;
-use mender_api::{Devices, Session};
+use mender_api::dto::{Predicate, Search};
+use mender_api::{Devices, Inventory, Session};
 use uuid::Uuid;
 
+use self::export::{Format, export};
+use self::snapshot::{diff, load, print_diff, save};
 use crate::util::OrBail;
 
+mod export;
+mod snapshot;
+
 #[derive(Debug, Subcommand)]
 pub enum DeviceAction {
     List {
@@ -33,9 +40,87 @@ pub enum DeviceAction {
         #[clap(long, short = 'p', help = "Page size for device listing")]
         page_size: Option<NonZero<usize>>,
     },
+    Search {
+        #[clap(long, short = 'm', help = "Find devices with the given MAC address")]
+        mac_address: Option<MacAddr6>,
+        #[clap(long, short = 'H', help = "Find devices with the given host name")]
+        hostname: Option<String>,
+        #[clap(
+            long,
+            short = 'a',
+            help = "Find devices with the given artifact installed"
+        )]
+        artifact_name: Option<String>,
+        #[clap(
+            long,
+            short = 'r',
+            help = "Find devices with the given root filesystem image version"
+        )]
+        rootfs_image_version: Option<String>,
+        #[clap(long, short = 't', help = "Find devices of the given device type")]
+        device_type: Option<String>,
+        #[clap(long, short = 'p', help = "Page size for device listing")]
+        page_size: Option<NonZero<usize>>,
+        #[clap(long, short = 'v', help = "List detailed device information")]
+        verbose: bool,
+    },
+    Export {
+        #[clap(long, short = 'f', default_value = "csv", help = "Output format")]
+        format: Format,
+        #[clap(
+            long,
+            short = 'c',
+            value_delimiter = ',',
+            help = "Columns to export, e.g. id,identity:mac,inventory:hostname (default: all)"
+        )]
+        columns: Vec<String>,
+        #[clap(long, short = 'o', help = "Output file (default: stdout)")]
+        output: Option<PathBuf>,
+        #[clap(long, short = 'p', help = "Page size for device listing")]
+        page_size: Option<NonZero<usize>>,
+    },
+    Snapshot {
+        #[clap(long, short = 'o', help = "Output file (default: stdout)")]
+        output: Option<PathBuf>,
+        #[clap(long, short = 'p', help = "Page size for device listing")]
+        page_size: Option<NonZero<usize>>,
+    },
+    Diff {
+        #[clap(index = 1, help = "Path to the old inventory snapshot")]
+        old: PathBuf,
+        #[clap(
+            index = 2,
+            help = "Path to the new inventory snapshot (default: current inventory)"
+        )]
+        new: Option<PathBuf>,
+        #[clap(
+            long,
+            short = 'i',
+            help = "Ignore changes of the given attribute, e.g. system:updated_ts"
+        )]
+        ignore: Vec<String>,
+        #[clap(long, short = 'p', help = "Page size for device listing")]
+        page_size: Option<NonZero<usize>>,
+    },
 }
 
 impl DeviceAction {
+    /// Run the action if it does not require a session.
+    pub fn run_offline(&self) -> Option<Result<(), ExitCode>> {
+        match self {
+            Self::Diff {
+                old,
+                new: Some(new),
+                ignore,
+                ..
+            } => Some(load(old).and_then(|old| {
+                print_diff(&old, &load(new)?, ignore);
+                Ok(())
+            })),
+            _ => None,
+        }
+    }
+
     pub async fn run(self, session: &Session) -> Result<(), ExitCode> {
         match self {
             Self::List { page_size, verbose } => {
@@ -70,15 +155,13 @@ impl DeviceAction {
                 mac_address,
                 page_size,
             } => {
-                let mut devices = Vec::new();
-                let mut iterator = Devices::list(session, page_size);
-
-                while let Some(device) = iterator.next().await {
-                    let device = device.or_bail()?;
-                    if device.mac_address().is_some_and(|addr| addr == mac_address) {
-                        devices.push(device);
-                    }
-                }
+                let devices = Inventory::search_collect(
+                    session,
+                    &Search::new().with_filter(Predicate::mac_address(mac_address)),
+                    page_size,
+                )
+                .await
+                .or_bail()?;
 
                 if devices.is_empty() {
                     return Err(ExitCode::FAILURE);
@@ -88,6 +171,55 @@ impl DeviceAction {
                     println!("{device}");
                 }
             }
+            Self::Search {
+                mac_address,
+                hostname,
+                artifact_name,
+                rootfs_image_version,
+                device_type,
+                page_size,
+                verbose,
+            } => {
+                let search: Search = mac_address
+                    .map(Predicate::mac_address)
+                    .into_iter()
+                    .chain(hostname.map(Predicate::hostname))
+                    .chain(artifact_name.map(Predicate::artifact_name))
+                    .chain(rootfs_image_version.map(Predicate::rootfs_image_version))
+                    .chain(device_type.map(Predicate::device_type))
+                    .collect();
+                let mut devices = Inventory::search(session, &search, page_size);
+
+                while let Some(device) = devices.next().await {
+                    let device = device.or_bail()?;
+
+                    if verbose {
+                        println!("{device:#}");
+                    } else {
+                        println!("{device}");
+                    }
+                }
+            }
+            Self::Export {
+                format,
+                columns,
+                output,
+                page_size,
+            } => {
+                export(session, format, columns, output.as_deref(), page_size).await?;
+            }
+            Self::Snapshot { output, page_size } => {
+                let devices = Devices::collect(session, page_size).await.or_bail()?;
+                save(&devices, output.as_deref())?;
+            }
+            Self::Diff {
+                old,
+                new,
+                ignore,
+                page_size,
+            } => {
+                diff(session, &old, new.as_deref(), &ignore, page_size).await?;
+            }
         }
 
         Ok(())
@@ -0,0 +1,154 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::iter::once;
+use std::num::NonZero;
+use std::path::Path;
+use std::process::ExitCode;
+
+use clap::ValueEnum;
+use mender_api::dto::{Device, VALUE_SEPARATOR};
+use mender_api::{Devices, Session};
+use serde_json::{Map, Value};
+
+use crate::util::OrBail;
+
+const ID: &str = "id";
+const UPDATED_TS: &str = "updated_ts";
+
+/// Output format of device exports.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Comma-separated values with a header row.
+    #[default]
+    Csv,
+    /// One JSON object per line.
+    JsonLines,
+}
+
+/// Export all devices with the given columns.
+///
+/// If no columns are given, all attributes of all devices are exported. In that case the devices
+/// are listed twice: once to collect the column names and once to write the rows, so that the
+/// inventory never has to be held in memory as a whole. Attributes that first appear between the
+/// two listings are not exported.
+pub async fn export(
+    session: &Session,
+    format: Format,
+    columns: Vec<String>,
+    output: Option<&Path>,
+    page_size: Option<NonZero<usize>>,
+) -> Result<(), ExitCode> {
+    let writer: Box<dyn Write + Send> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path).or_bail()?)),
+        None => Box::new(io::stdout()),
+    };
+    let mut writer = RowWriter::new(format, writer);
+    let columns = if columns.is_empty() {
+        all_columns(session, page_size).await?
+    } else {
+        columns
+    };
+    writer.header(&columns).or_bail()?;
+    let mut pages = Devices::pages(session, page_size);
+
+    while let Some(page) = pages.next().await {
+        for device in page.or_bail()? {
+            writer.row(&columns, &row(&device)).or_bail()?;
+        }
+    }
+
+    writer.flush().or_bail()
+}
+
+/// Collect the names of all columns of all devices, starting with the device ID.
+async fn all_columns(
+    session: &Session,
+    page_size: Option<NonZero<usize>>,
+) -> Result<Vec<String>, ExitCode> {
+    let mut columns = BTreeSet::new();
+    let mut pages = Devices::pages(session, page_size);
+
+    while let Some(page) = pages.next().await {
+        for device in page.or_bail()? {
+            columns.extend(row(&device).into_keys().filter(|column| column != ID));
+        }
+    }
+
+    Ok(once(ID.into()).chain(columns).collect())
+}
+
+/// Flatten a device into a map of column names to values.
+///
+/// Attributes are named `scope:name`. Values of attributes with the same name are joined by
+/// [`VALUE_SEPARATOR`].
+fn row(device: &Device) -> BTreeMap<String, String> {
+    let mut row = BTreeMap::new();
+    row.insert(ID.into(), device.id().to_string());
+    row.insert(UPDATED_TS.into(), device.updated_ts().to_rfc3339());
+
+    for attribute in device.attributes() {
+        let value = attribute.value_to_string();
+        row.entry(format!("{}:{}", attribute.scope(), attribute.name()))
+            .and_modify(|existing: &mut String| {
+                existing.push_str(VALUE_SEPARATOR);
+                existing.push_str(&value);
+            })
+            .or_insert(value);
+    }
+
+    row
+}
+
+/// Writes rows in the selected format.
+enum RowWriter {
+    Csv(Box<csv::Writer<Box<dyn Write + Send>>>),
+    JsonLines(Box<dyn Write + Send>),
+}
+
+impl RowWriter {
+    fn new(format: Format, writer: Box<dyn Write + Send>) -> Self {
+        match format {
+            Format::Csv => Self::Csv(Box::new(csv::Writer::from_writer(writer))),
+            Format::JsonLines => Self::JsonLines(writer),
+        }
+    }
+
+    fn header(&mut self, columns: &[String]) -> csv::Result<()> {
+        match self {
+            Self::Csv(writer) => writer.write_record(columns),
+            Self::JsonLines(_) => Ok(()),
+        }
+    }
+
+    fn row(&mut self, columns: &[String], row: &BTreeMap<String, String>) -> csv::Result<()> {
+        match self {
+            Self::Csv(writer) => writer.write_record(
+                columns
+                    .iter()
+                    .map(|column| row.get(column).map_or("", String::as_str)),
+            ),
+            Self::JsonLines(writer) => {
+                let object: Map<String, Value> = columns
+                    .iter()
+                    .map(|column| {
+                        (
+                            column.clone(),
+                            row.get(column)
+                                .map_or(Value::Null, |value| value.clone().into()),
+                        )
+                    })
+                    .collect();
+                serde_json::to_writer(&mut *writer, &object).map_err(io::Error::from)?;
+                writeln!(writer).map_err(Into::into)
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Csv(writer) => writer.flush(),
+            Self::JsonLines(writer) => writer.flush(),
+        }
+    }
+}
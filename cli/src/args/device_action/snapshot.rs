@@ -0,0 +1,53 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::num::NonZero;
+use std::path::Path;
+use std::process::ExitCode;
+
+use mender_api::dto::Device;
+use mender_api::{Devices, Session};
+
+use crate::util::OrBail;
+
+/// Load an inventory snapshot from a JSON file.
+pub fn load(path: &Path) -> Result<Vec<Device>, ExitCode> {
+    serde_json::from_reader(BufReader::new(File::open(path).or_bail()?)).or_bail()
+}
+
+/// Save an inventory snapshot as JSON to the given file or stdout.
+pub fn save(devices: &[Device], output: Option<&Path>) -> Result<(), ExitCode> {
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path).or_bail()?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    serde_json::to_writer_pretty(&mut writer, devices).or_bail()?;
+    writeln!(writer).or_bail()?;
+    writer.flush().or_bail()
+}
+
+/// Print the differences between an old snapshot and a new snapshot or the current inventory.
+///
+/// Changes of the attributes to ignore, given as `scope:name`, are omitted.
+pub async fn diff(
+    session: &Session,
+    old: &Path,
+    new: Option<&Path>,
+    ignore: &[String],
+    page_size: Option<NonZero<usize>>,
+) -> Result<(), ExitCode> {
+    let old = load(old)?;
+    let new = match new {
+        Some(new) => load(new)?,
+        None => Devices::collect(session, page_size).await.or_bail()?,
+    };
+    print_diff(&old, &new, ignore);
+    Ok(())
+}
+
+/// Print the differences between two snapshots, omitting changes of the attributes to ignore.
+pub fn print_diff(old: &[Device], new: &[Device], ignore: &[String]) {
+    let diff = mender_api::inventory_diff::diff(old, new).retain_changes(|change| {
+        !ignore.contains(&format!("{}:{}", change.scope(), change.name()))
+    });
+    print!("{diff}");
+}
@@ -0,0 +1,140 @@
+use std::fs::read_to_string;
+use std::num::NonZero;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Subcommand;
+use log::{error, info};
+use macaddr::MacAddr6;
+use mender_api::dto::{Identity, Status};
+use mender_api::{DeviceAuth, Session};
+use uuid::Uuid;
+
+use crate::util::OrBail;
+
+#[derive(Debug, Subcommand)]
+pub enum DeviceAuthAction {
+    List {
+        #[clap(long, short = 's', help = "List only devices with the given status")]
+        status: Option<Status>,
+        #[clap(long, short = 'p', help = "Page size for device listing")]
+        page_size: Option<NonZero<usize>>,
+        #[clap(long, short = 'v', help = "List detailed device information")]
+        verbose: bool,
+    },
+    Get {
+        #[clap(index = 1, help = "ID of the device to retrieve")]
+        id: Uuid,
+    },
+    Accept {
+        #[clap(index = 1, help = "ID of the device")]
+        id: Uuid,
+        #[clap(index = 2, help = "ID of the authentication set to accept")]
+        auth_set_id: Uuid,
+    },
+    AcceptPending {
+        #[clap(long, short = 'p', help = "Page size for device listing")]
+        page_size: Option<NonZero<usize>>,
+    },
+    Reject {
+        #[clap(index = 1, help = "ID of the device")]
+        id: Uuid,
+        #[clap(index = 2, help = "ID of the authentication set to reject")]
+        auth_set_id: Uuid,
+    },
+    Dismiss {
+        #[clap(index = 1, help = "ID of the device")]
+        id: Uuid,
+        #[clap(index = 2, help = "ID of the authentication set to dismiss")]
+        auth_set_id: Uuid,
+    },
+    Decommission {
+        #[clap(index = 1, help = "ID of the device to decommission")]
+        id: Uuid,
+    },
+    Preauthorize {
+        #[clap(index = 1, help = "MAC address of the device")]
+        mac_address: MacAddr6,
+        #[clap(index = 2, help = "Path to the device's public key in PEM format")]
+        pubkey: PathBuf,
+        #[clap(long, short = 'f', help = "Overwrite an existing, not accepted device")]
+        force: bool,
+    },
+}
+
+impl DeviceAuthAction {
+    pub async fn run(self, session: &Session) -> Result<(), ExitCode> {
+        match self {
+            Self::List {
+                status,
+                page_size,
+                verbose,
+            } => {
+                let mut devices = DeviceAuth::list(session, status, page_size);
+
+                while let Some(result) = devices.next().await {
+                    match result {
+                        Ok(device) => {
+                            if verbose {
+                                println!("{device:#}");
+                            } else {
+                                println!("{device}");
+                            }
+                        }
+                        Err(error) => {
+                            error!("{error}");
+                            return Err(ExitCode::FAILURE);
+                        }
+                    }
+                }
+            }
+            Self::Get { id } => {
+                let device = DeviceAuth::get(session, id).await.or_bail()?;
+                println!("{device:#}");
+            }
+            Self::Accept { id, auth_set_id } => {
+                DeviceAuth::accept(session, id, auth_set_id)
+                    .await
+                    .or_bail()?;
+            }
+            Self::AcceptPending { page_size } => {
+                for device in DeviceAuth::collect(session, Some(Status::Pending), page_size)
+                    .await
+                    .or_bail()?
+                {
+                    for auth_set in device.auth_sets_with_status(Status::Pending) {
+                        DeviceAuth::accept(session, device.id(), auth_set.id())
+                            .await
+                            .or_bail()?;
+                        info!("Accepted auth set {} of device {device}", auth_set.id());
+                    }
+                }
+            }
+            Self::Reject { id, auth_set_id } => {
+                DeviceAuth::reject(session, id, auth_set_id)
+                    .await
+                    .or_bail()?;
+            }
+            Self::Dismiss { id, auth_set_id } => {
+                DeviceAuth::dismiss(session, id, auth_set_id)
+                    .await
+                    .or_bail()?;
+            }
+            Self::Decommission { id } => {
+                DeviceAuth::decommission(session, id).await.or_bail()?;
+            }
+            Self::Preauthorize {
+                mac_address,
+                pubkey,
+                force,
+            } => {
+                let pubkey = read_to_string(pubkey).or_bail()?;
+                DeviceAuth::preauthorize(session, Identity::new(mac_address), pubkey, force)
+                    .await
+                    .or_bail()?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use std::num::NonZero;
+use std::process::ExitCode;
+
+use clap::Subcommand;
+use mender_api::dto::Predicate;
+use mender_api::{InventoryFilters, Session};
+use uuid::Uuid;
+
+use crate::util::OrBail;
+
+#[derive(Debug, Subcommand)]
+pub enum FilterAction {
+    List {
+        #[clap(long, short = 'p', help = "Page size for filter listing")]
+        page_size: Option<NonZero<usize>>,
+        #[clap(long, short = 'v', help = "List detailed filter information")]
+        verbose: bool,
+    },
+    Get {
+        #[clap(index = 1, help = "ID of the filter to retrieve")]
+        id: Uuid,
+    },
+    Create {
+        #[clap(index = 1, help = "Name of the filter")]
+        name: String,
+        #[clap(
+            index = 2,
+            required = true,
+            help = "Terms of the filter as SCOPE/ATTRIBUTE $OPERATOR VALUE"
+        )]
+        terms: Vec<Predicate>,
+    },
+    Delete {
+        #[clap(index = 1, help = "ID of the filter to delete")]
+        id: Uuid,
+    },
+    Devices {
+        #[clap(index = 1, help = "ID of the filter whose matching devices to list")]
+        id: Uuid,
+        #[clap(long, short = 'p', help = "Page size for device listing")]
+        page_size: Option<NonZero<usize>>,
+        #[clap(long, short = 'v', help = "List detailed device information")]
+        verbose: bool,
+    },
+}
+
+impl FilterAction {
+    pub async fn run(self, session: &Session) -> Result<(), ExitCode> {
+        match self {
+            Self::List { page_size, verbose } => {
+                let mut filters = InventoryFilters::list(session, page_size);
+
+                while let Some(filter) = filters.next().await {
+                    let filter = filter.or_bail()?;
+
+                    if verbose {
+                        println!("{filter:#}");
+                    } else {
+                        println!("{filter}");
+                    }
+                }
+            }
+            Self::Get { id } => {
+                let filter = InventoryFilters::get(session, id).await.or_bail()?;
+                println!("{filter:#}");
+            }
+            Self::Create { name, terms } => {
+                match InventoryFilters::create(session, name, &terms)
+                    .await
+                    .or_bail()?
+                {
+                    Some(id) => println!("Filter {id} created successfully."),
+                    None => println!("Filter created successfully."),
+                }
+            }
+            Self::Delete { id } => {
+                InventoryFilters::delete(session, id).await.or_bail()?;
+            }
+            Self::Devices {
+                id,
+                page_size,
+                verbose,
+            } => {
+                for device in InventoryFilters::devices_of(session, id, page_size)
+                    .await
+                    .or_bail()?
+                {
+                    if verbose {
+                        println!("{device:#}");
+                    } else {
+                        println!("{device}");
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
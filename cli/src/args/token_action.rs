@@ -0,0 +1,63 @@
+use std::num::NonZero;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::Subcommand;
+use mender_api::{PersonalAccessTokens, Session};
+use uuid::Uuid;
+
+use crate::util::OrBail;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Subcommand)]
+pub enum TokenAction {
+    List {
+        #[clap(long, short = 'v', help = "List detailed token information")]
+        verbose: bool,
+    },
+    Create {
+        #[clap(index = 1, help = "Name of the token")]
+        name: String,
+        #[clap(
+            long,
+            short = 'e',
+            default_value = "30",
+            help = "Number of days until the token expires"
+        )]
+        expires_in: NonZero<u64>,
+    },
+    Revoke {
+        #[clap(index = 1, help = "ID of the token to revoke")]
+        id: Uuid,
+    },
+}
+
+impl TokenAction {
+    pub async fn run(self, session: &Session) -> Result<(), ExitCode> {
+        match self {
+            Self::List { verbose } => {
+                for token in PersonalAccessTokens::list(session).await.or_bail()? {
+                    if verbose {
+                        println!("{token:#}");
+                    } else {
+                        println!("{token}");
+                    }
+                }
+            }
+            Self::Create { name, expires_in } => {
+                let expires_in =
+                    Duration::from_secs(expires_in.get().saturating_mul(SECONDS_PER_DAY));
+                let token = PersonalAccessTokens::create(session, name, expires_in)
+                    .await
+                    .or_bail()?;
+                println!("{token}");
+            }
+            Self::Revoke { id } => {
+                PersonalAccessTokens::revoke(session, id).await.or_bail()?;
+            }
+        }
+
+        Ok(())
+    }
+}